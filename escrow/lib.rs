@@ -1,36 +0,0 @@
-pub mod constants;
-pub mod error;
-pub mod instructions;
-pub mod state;
-
-use anchor_lang::prelude::*;
-
-pub use constants::*;
-pub use instructions::*;
-pub use state::*;
-
-declare_id!("2m3HqaV8HghNRkBqzgkAJchrpEdH1P42wQwQbyf5W723");
-
-#[program]
-pub mod escrow {
-    use super::*;
-
-    pub fn make(ctx: Context<Make>, seed: u64, receive_amount: u64) -> Result<()> {
-        ctx.accounts.make_offer(seed, receive_amount, &ctx.bumps)?;
-        ctx.accounts.deposit(receive_amount)? ;
-        Ok(())
-    }
-
-    pub fn refund(ctx: Context<Refund>) -> Result<()> {
-        ctx.accounts.refund()?;
-        ctx.accounts.close()?;
-        Ok(())
-    }
-
-    pub fn take(ctx: Context<Take>) -> Result<()> {
-        ctx.accounts.deposit()?;
-        ctx.accounts.release()?;
-        ctx.accounts.close()?;
-        Ok(())
-    }
-}
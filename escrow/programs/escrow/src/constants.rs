@@ -0,0 +1,7 @@
+pub const CRANK_REWARD: u64 = 10_000; // lamports taken from the escrow rent for calling expire
+
+pub const MAX_BUNDLE_LEGS: usize = 5;
+
+pub const MAX_BOOK_PAGE_LEN: usize = 32;
+
+pub const MAX_FEE_BPS: u16 = 1_000; // 10% ceiling on the protocol fee
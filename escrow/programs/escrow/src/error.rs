@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Fill Amount Must Be Between Zero And The Remaining Receive Amount")]
+    InvalidFillAmount,
+    #[msg("Fill Too Small To Release Any Deposit")]
+    FillTooSmall,
+    #[msg("Expiry Must Be In The Future")]
+    InvalidExpiry,
+    #[msg("Offer Has Expired")]
+    OfferExpired,
+    #[msg("Offer Has Not Expired Yet")]
+    OfferNotExpired,
+    #[msg("Offer Is Reserved For Another Taker")]
+    UnauthorizedTaker,
+    #[msg("Bundle Legs Are Empty, Too Many, Zero Or Duplicated")]
+    InvalidBundle,
+    #[msg("Remaining Accounts Do Not Match The Bundle Legs")]
+    InvalidBundleAccounts,
+    #[msg("Order Book Page Is Full")]
+    BookPageFull,
+    #[msg("Order Book Page Must Exist Or Be The Next One")]
+    InvalidBookPage,
+    #[msg("Token Account Required For A Non-Native Leg")]
+    MissingTokenAccount,
+    #[msg("Native SOL Legs Are Not Supported By This Instruction")]
+    NativeLegUnsupported,
+    #[msg("Signer Is Not The Program Upgrade Authority")]
+    NotUpgradeAuthority,
+    #[msg("Protocol Fee Exceeds The Maximum")]
+    FeeTooHigh,
+    #[msg("Deposit And Receive Amounts Must Be Greater Than Zero")]
+    ZeroAmount,
+    #[msg("Offered And Requested Mints Must Differ")]
+    IdenticalMints,
+    #[msg("Vault Holds Less Than The Amount To Release")]
+    InsufficientVaultBalance,
+    #[msg("Mint Does Not Match The Escrow")]
+    MintMismatch,
+    #[msg("Offer Deposit Changed Since The Counter Was Made")]
+    CounterOfferStale,
+    #[msg("Offer Terms Changed Since The Taker Quoted Them")]
+    OfferTermsChanged,
+    #[msg("Arithmetic Overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::error::EscrowError;
+use crate::instructions::config::Config;
+use crate::instructions::counter::CounterOffer;
+use crate::instructions::escrow::Escrow;
+use crate::instructions::order_book::OrderBook;
+
+#[derive(Accounts)]
+pub struct AcceptCounter<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(mut)]
+    pub taker: SystemAccount<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_b: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        has_one = mint_b @ EscrowError::MintMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = taker,
+        has_one = escrow,
+        has_one = taker,
+        seeds = [b"counter", escrow.key().as_ref(), taker.key().as_ref()],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, CounterOffer>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter,
+        associated_token::token_program = token_program,
+    )]
+    pub counter_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref(), escrow.book_page.to_le_bytes().as_ref()],
+        bump = book.bump,
+    )]
+    pub book: Account<'info, OrderBook>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        address = config.treasury,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Account<'info, TokenAccount>>, // omitted when no fee is due
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> AcceptCounter<'info> {
+    pub fn pay_maker(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.escrow.expires_at,
+            EscrowError::OfferExpired
+        );
+        // An amend or partial fill since the counter would sell the taker less than they quoted for.
+        require!(self.escrow.deposit == self.counter.deposit, EscrowError::CounterOfferStale);
+
+        let escrow = self.escrow.key();
+        let taker = self.taker.key();
+        let counter_seeds: &[&[&[u8]]] = &[&[b"counter", escrow.as_ref(), taker.as_ref(), &[self.counter.bump]]];
+
+        let fee = self.config.fee(self.counter.receive);
+        if fee > 0 {
+            let Some(treasury_ata_b) = &self.treasury_ata_b else {
+                return err!(EscrowError::MissingTokenAccount);
+            };
+
+            let cpi_accounts = TransferChecked {
+                from: self.counter_vault.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.counter.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, counter_seeds);
+            transfer_checked(cpi_ctx, fee, self.mint_b.decimals)?;
+        }
+
+        let cpi_accounts = TransferChecked {
+            from: self.counter_vault.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, counter_seeds);
+        transfer_checked(cpi_ctx, self.counter_vault.amount - fee, self.mint_b.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.counter_vault.to_account_info(),
+            destination: self.taker.to_account_info(),
+            authority: self.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, counter_seeds);
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+
+    pub fn release(&mut self) -> Result<()> {
+        let maker = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let escrow_seeds: &[&[&[u8]]] = &[&[b"escrow", maker.as_ref(), seed.as_ref(), &[self.escrow.bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, escrow_seeds);
+        // Sweeps the whole vault so tokens donated to it can't block the close below.
+        transfer_checked(cpi_ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, escrow_seeds);
+        close_account(cpi_ctx)?;
+
+        self.book.remove(&self.escrow.key());
+
+        Ok(())
+    }
+}
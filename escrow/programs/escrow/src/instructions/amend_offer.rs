@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::error::EscrowError;
+use crate::instructions::escrow::Escrow;
+
+#[derive(Accounts)]
+pub struct AmendOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> AmendOffer<'info> {
+    pub fn amend_offer(&mut self, deposit: u64, receive: u64) -> Result<()> {
+        require!(!Escrow::is_native(&self.mint_a.key()), EscrowError::NativeLegUnsupported);
+        require!(deposit > 0 && receive > 0, EscrowError::ZeroAmount);
+
+        let current = self.escrow.deposit;
+
+        if deposit > current {
+            let cpi_accounts = TransferChecked {
+                from: self.maker_ata_a.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.vault.to_account_info(),
+                authority: self.maker.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            transfer_checked(cpi_ctx, deposit - current, self.mint_a.decimals)?;
+        } else if deposit < current {
+            let cpi_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let maker = self.maker.key();
+            let seed = self.escrow.seed.to_le_bytes();
+            let escrow_seeds: &[&[&[u8]]] = &[&[b"escrow", maker.as_ref(), seed.as_ref(), &[self.escrow.bump]]];
+
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, escrow_seeds);
+            transfer_checked(cpi_ctx, current - deposit, self.mint_a.decimals)?;
+        }
+
+        self.escrow.deposit = deposit;
+        self.escrow.receive = receive;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::constants::MAX_BUNDLE_LEGS;
+use crate::error::EscrowError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Leg {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl Leg {
+    pub fn validate(legs: &[Leg]) -> Result<()> {
+        require!(legs.len() <= MAX_BUNDLE_LEGS, EscrowError::InvalidBundle);
+
+        for (i, leg) in legs.iter().enumerate() {
+            require!(leg.amount > 0, EscrowError::InvalidBundle);
+            require!(
+                !legs[..i].iter().any(|other| other.mint == leg.mint),
+                EscrowError::InvalidBundle
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// Bundles are not listed in the order book: a book page indexes offers for a single mint pair.
+#[account]
+#[derive(InitSpace)]
+pub struct BundleEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    #[max_len(MAX_BUNDLE_LEGS)]
+    pub offered: Vec<Leg>, // each held in an escrow-owned ATA
+    #[max_len(MAX_BUNDLE_LEGS)]
+    pub requested: Vec<Leg>,
+    pub requested_lamports: u64,
+    pub expires_at: i64,
+    pub taker: Option<Pubkey>, // only this key can take when set
+    pub bump: u8,
+}
+
+// Sends an offered leg out of its vault and closes the vault. Anything donated to the
+// vault on top of the leg goes to `leftover_to`, so dust can't keep the vault open.
+#[allow(clippy::too_many_arguments)]
+pub fn release_leg<'info>(
+    escrow: &Account<'info, BundleEscrow>,
+    leg: &Leg,
+    mint: &'info AccountInfo<'info>,
+    vault: &'info AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    leftover_to: AccountInfo<'info>,
+    rent_receiver: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let mint_account = Account::<Mint>::try_from(mint)?;
+    let vault_account = Account::<TokenAccount>::try_from(vault)?;
+    require_keys_eq!(mint.key(), leg.mint, EscrowError::InvalidBundleAccounts);
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address(&escrow.key(), &leg.mint),
+        EscrowError::InvalidBundleAccounts
+    );
+
+    let seed = escrow.seed.to_le_bytes();
+    let escrow_seeds: &[&[&[u8]]] = &[&[b"bundle", escrow.maker.as_ref(), seed.as_ref(), &[escrow.bump]]];
+
+    let leftover = vault_account.amount.checked_sub(leg.amount).ok_or(EscrowError::InsufficientVaultBalance)?;
+    let (amount, leftover) = if to.key() == leftover_to.key() {
+        (vault_account.amount, 0)
+    } else {
+        (leg.amount, leftover)
+    };
+
+    let cpi_accounts = TransferChecked {
+        from: vault.clone(),
+        mint: mint.clone(),
+        to,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, escrow_seeds);
+    transfer_checked(cpi_ctx, amount, mint_account.decimals)?;
+
+    if leftover > 0 {
+        let cpi_accounts = TransferChecked {
+            from: vault.clone(),
+            mint: mint.clone(),
+            to: leftover_to,
+            authority: escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, escrow_seeds);
+        transfer_checked(cpi_ctx, leftover, mint_account.decimals)?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: vault.clone(),
+        destination: rent_receiver,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, escrow_seeds);
+    close_account(cpi_ctx)
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee_bps: u16, // skimmed from what the maker is paid on every settlement
+    pub treasury: Pubkey, // wallet that receives the fee
+    pub bump: u8,
+}
+
+impl Config {
+    // Rounds down, so no fill pays more than fee_bps of its amount. A fill too small to owe a
+    // whole unit pays nothing, which saves the taker less than the transaction costs them.
+    pub fn fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128 / 10_000) as u64
+    }
+}
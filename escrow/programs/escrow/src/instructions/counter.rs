@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CounterOffer {
+    pub escrow: Pubkey,
+    pub taker: Pubkey,
+    pub deposit: u64, // remaining deposit the counter was quoted against
+    pub receive: u64, // mint B the taker escrowed for the whole remaining deposit
+    pub bump: u8,
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 
 #[account]
 #[derive(InitSpace)]
@@ -7,6 +8,17 @@ pub struct Escrow {
     pub maker: Pubkey,
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
-    pub receive: u64,
-    pub bump: u64
+    pub deposit: u64, // mint A still held in the vault
+    pub receive: u64, // mint B still owed for the remaining deposit
+    pub expires_at: i64,
+    pub taker: Option<Pubkey>, // only this key can fill when set
+    pub book_page: u32, // order book page listing this offer
+    pub bump: u8,
+}
+
+impl Escrow {
+    // Legs on the native mint are settled in lamports held by the escrow PDA itself.
+    pub fn is_native(mint: &Pubkey) -> bool {
+        *mint == native_mint::ID
+    }
 }
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::constants::CRANK_REWARD;
+use crate::error::EscrowError;
+use crate::instructions::escrow::Escrow;
+use crate::instructions::order_book::OrderBook;
+
+#[derive(Accounts)]
+pub struct Expire<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    // A maker who closed their ATA can't strand the offer: the cranker pays to recreate it and still earns the reward.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>, // omitted when mint A is native
+
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref(), escrow.book_page.to_le_bytes().as_ref()],
+        bump = book.bump,
+    )]
+    pub book: Account<'info, OrderBook>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Expire<'info> {
+    pub fn refund(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.escrow.expires_at,
+            EscrowError::OfferNotExpired
+        );
+
+        // Native deposits sit in the escrow's own lamports and go back when it closes.
+        if Escrow::is_native(&self.mint_a.key()) {
+            return Ok(());
+        }
+
+        let (Some(vault), Some(maker_ata_a)) = (&self.vault, &self.maker_ata_a) else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let maker = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let escrow_seeds: &[&[&[u8]]] = &[&[b"escrow", maker.as_ref(), seed.as_ref(), &[self.escrow.bump]]];
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, escrow_seeds);
+        transfer_checked(cpi_ctx, vault.amount, self.mint_a.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        if let Some(vault) = &self.vault {
+            let cpi_accounts = CloseAccount {
+                account: vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let maker = self.maker.key();
+            let seed = self.escrow.seed.to_le_bytes();
+            let escrow_seeds: &[&[&[u8]]] = &[&[b"escrow", maker.as_ref(), seed.as_ref(), &[self.escrow.bump]]];
+
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, escrow_seeds);
+            close_account(cpi_ctx)?;
+        }
+
+        self.book.remove(&self.escrow.key());
+
+        // The cranker's cut comes out of the escrow rent; the maker gets the rest.
+        **self.escrow.to_account_info().try_borrow_mut_lamports()? -= CRANK_REWARD;
+        **self.cranker.to_account_info().try_borrow_mut_lamports()? += CRANK_REWARD;
+
+        self.escrow.close(self.maker.to_account_info())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_FEE_BPS;
+use crate::error::EscrowError;
+use crate::instructions::config::Config;
+use crate::program::Escrow as EscrowProgram;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = this_program.programdata_address()? == Some(program_data.key()),
+    )]
+    pub this_program: Program<'info, EscrowProgram>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ EscrowError::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(&mut self, fee_bps: u16, treasury: Pubkey, bumps: &InitializeConfigBumps) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, EscrowError::FeeTooHigh);
+
+        self.config.set_inner(Config {
+            admin: self.admin.key(),
+            fee_bps,
+            treasury,
+            bump: bumps.config,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, fee_bps: u16, treasury: Pubkey, new_admin: Option<Pubkey>) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, EscrowError::FeeTooHigh);
+
+        self.config.fee_bps = fee_bps;
+        self.config.treasury = treasury;
+        if let Some(new_admin) = new_admin {
+            self.config.admin = new_admin;
+        }
+
+        Ok(())
+    }
+}
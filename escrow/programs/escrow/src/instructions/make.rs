@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
+use crate::error::EscrowError;
+use crate::constants::MAX_BOOK_PAGE_LEN;
 use crate::instructions::escrow::Escrow;
+use crate::instructions::order_book::{BookIndex, OrderBook};
 
 #[derive(Accounts)]
-#[instruction(seed: u64)]
+#[instruction(seed: u64, deposit_amount: u64, receive_amount: u64, expires_at: i64, taker: Option<Pubkey>, book_page: u32)]
 pub struct Make<'info> {
     #[account(mut)]
     pub maker: Signer<'info>,
@@ -21,37 +25,48 @@ pub struct Make<'info> {
     pub mint_b: Account<'info, Mint>,
 
     #[account(
-        init,
-        payer = maker,
+        mut,
         associated_token::mint = mint_a,
         associated_token::authority = maker,
+        associated_token::token_program = token_program,
     )]
-    pub maker_ata_a: Account<'info, TokenAccount>,
+    pub maker_ata_a: Option<Account<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = maker,
-        associated_token::mint = mint_b,
-        associated_token::authority = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub maker_ata_b: Account<'info, TokenAccount>,
+    pub escrow: Account<'info, Escrow>,
 
     #[account(
         init,
         payer = maker,
-        space = 8 + Escrow::INIT_SPACE,
-        seeds = [b"escrow", maker.key().as_ref()],
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>, // omitted when mint A is native
+
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = 8 + BookIndex::INIT_SPACE,
+        seeds = [b"book_index", mint_a.key().as_ref(), mint_b.key().as_ref()],
         bump,
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub book_index: Account<'info, BookIndex>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = maker,
-        associated_token::mint = mint_a,
-        associated_token::authority = maker,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref(), book_page.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub book: Account<'info, OrderBook>,
 
     pub system_program: Program<'info, System>,
 
@@ -61,22 +76,44 @@ pub struct Make<'info> {
 }
 
 impl<'info> Make<'info> {
-    pub fn make_offer(&mut self, seed: u64, receive: u64, bump: &MakeBumps) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn make_offer(&mut self, seed: u64, deposit: u64, receive: u64, expires_at: i64, taker: Option<Pubkey>, book_page: u32, bump: &MakeBumps) -> Result<()> {
+        require!(expires_at > Clock::get()?.unix_timestamp, EscrowError::InvalidExpiry);
+        require!(deposit > 0 && receive > 0, EscrowError::ZeroAmount);
+        require_keys_neq!(self.mint_a.key(), self.mint_b.key(), EscrowError::IdenticalMints);
+
         self.escrow.set_inner(Escrow {
             seed,
             maker: self.maker.key(),
             mint_a: self.mint_a.key(),
             mint_b: self.mint_b.key(),
+            deposit,
             receive,
-            bump: bump.escrow as u64,
+            expires_at,
+            taker,
+            book_page,
+            bump: bump.escrow,
         });
         Ok(())
     }
 
     pub fn deposit(&mut self, deposit_amount: u64) -> Result<()> {
+        if Escrow::is_native(&self.mint_a.key()) {
+            let cpi_accounts = Transfer {
+                from: self.maker.to_account_info(),
+                to: self.escrow.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+            return transfer(cpi_ctx, deposit_amount);
+        }
+
+        let (Some(maker_ata_a), Some(vault)) = (&self.maker_ata_a, &self.vault) else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+
         let transfer_accounts = TransferChecked {
-            from: self.maker_ata_a.to_account_info(),
-            to: self.vault.to_account_info(),
+            from: maker_ata_a.to_account_info(),
+            to: vault.to_account_info(),
             mint: self.mint_a.to_account_info(),
             authority: self.maker.to_account_info(),
         };
@@ -87,4 +124,24 @@ impl<'info> Make<'info> {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn list(&mut self, book_page: u32, bump: &MakeBumps) -> Result<()> {
+        require!(book_page <= self.book_index.pages, EscrowError::InvalidBookPage);
+        require!(self.book.escrows.len() < MAX_BOOK_PAGE_LEN, EscrowError::BookPageFull);
+
+        if book_page == self.book_index.pages {
+            self.book_index.mint_a = self.mint_a.key();
+            self.book_index.mint_b = self.mint_b.key();
+            self.book_index.pages += 1;
+            self.book_index.bump = bump.book_index;
+        }
+
+        self.book.mint_a = self.mint_a.key();
+        self.book.mint_b = self.mint_b.key();
+        self.book.page = book_page;
+        self.book.bump = bump.book;
+        self.book.escrows.push(self.escrow.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create};
+use anchor_spl::token::{transfer_checked, Mint, Token, TransferChecked};
+
+use crate::error::EscrowError;
+use crate::instructions::bundle_escrow::{BundleEscrow, Leg};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeBundle<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + BundleEscrow::INIT_SPACE,
+        seeds = [b"bundle", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, BundleEscrow>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MakeBundle<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn make_bundle(&mut self, seed: u64, offered: Vec<Leg>, requested: Vec<Leg>, requested_lamports: u64, expires_at: i64, taker: Option<Pubkey>, bump: &MakeBundleBumps) -> Result<()> {
+        require!(expires_at > Clock::get()?.unix_timestamp, EscrowError::InvalidExpiry);
+        require!(!offered.is_empty(), EscrowError::InvalidBundle);
+        require!(!requested.is_empty() || requested_lamports > 0, EscrowError::InvalidBundle);
+        Leg::validate(&offered)?;
+        Leg::validate(&requested)?;
+
+        self.escrow.set_inner(BundleEscrow {
+            seed,
+            maker: self.maker.key(),
+            offered,
+            requested,
+            requested_lamports,
+            expires_at,
+            taker,
+            bump: bump.escrow,
+        });
+        Ok(())
+    }
+
+    // Remaining accounts: [mint, maker_ata, vault] for every offered leg.
+    pub fn deposit(&mut self, remaining: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            remaining.len() == self.escrow.offered.len() * 3,
+            EscrowError::InvalidBundleAccounts
+        );
+
+        for (leg, accounts) in self.escrow.offered.iter().zip(remaining.chunks(3)) {
+            let (mint, maker_ata, vault) = (&accounts[0], &accounts[1], &accounts[2]);
+
+            let mint_account = Account::<Mint>::try_from(mint)?;
+            require_keys_eq!(mint.key(), leg.mint, EscrowError::InvalidBundleAccounts);
+            require_keys_eq!(
+                vault.key(),
+                get_associated_token_address(&self.escrow.key(), &leg.mint),
+                EscrowError::InvalidBundleAccounts
+            );
+
+            let create_accounts = Create {
+                payer: self.maker.to_account_info(),
+                associated_token: vault.clone(),
+                authority: self.escrow.to_account_info(),
+                mint: mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            };
+            create_idempotent(CpiContext::new(self.associated_token_program.to_account_info(), create_accounts))?;
+
+            let transfer_accounts = TransferChecked {
+                from: maker_ata.clone(),
+                mint: mint.clone(),
+                to: vault.clone(),
+                authority: self.maker.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+            transfer_checked(cpi_ctx, leg.amount, mint_account.decimals)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::error::EscrowError;
+use crate::instructions::counter::CounterOffer;
+use crate::instructions::escrow::Escrow;
+
+#[derive(Accounts)]
+pub struct MakeCounter<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    pub maker: SystemAccount<'info>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Account<'info, TokenAccount>,
+
+    #[account(
+        has_one = maker,
+        has_one = mint_b @ EscrowError::MintMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.taker.is_none() || escrow.taker == Some(taker.key()) @ EscrowError::UnauthorizedTaker,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = taker,
+        space = 8 + CounterOffer::INIT_SPACE,
+        seeds = [b"counter", escrow.key().as_ref(), taker.key().as_ref()],
+        bump,
+    )]
+    pub counter: Account<'info, CounterOffer>,
+
+    #[account(
+        init,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter,
+        associated_token::token_program = token_program,
+    )]
+    pub counter_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MakeCounter<'info> {
+    pub fn make_counter(&mut self, receive: u64, bumps: &MakeCounterBumps) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.escrow.expires_at,
+            EscrowError::OfferExpired
+        );
+        require!(
+            !Escrow::is_native(&self.escrow.mint_a) && !Escrow::is_native(&self.escrow.mint_b),
+            EscrowError::NativeLegUnsupported
+        );
+        require!(receive > 0, EscrowError::ZeroAmount);
+
+        self.counter.set_inner(CounterOffer {
+            escrow: self.escrow.key(),
+            taker: self.taker.key(),
+            deposit: self.escrow.deposit,
+            receive,
+            bump: bumps.counter,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.counter_vault.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        transfer_checked(cpi_ctx, self.counter.receive, self.mint_b.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelCounter<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Account<'info, TokenAccount>,
+
+    // The escrow may already be filled or refunded, so only its key is needed.
+    #[account(
+        mut,
+        close = taker,
+        has_one = taker,
+        seeds = [b"counter", counter.escrow.as_ref(), taker.key().as_ref()],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, CounterOffer>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter,
+        associated_token::token_program = token_program,
+    )]
+    pub counter_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CancelCounter<'info> {
+    pub fn refund(&mut self) -> Result<()> {
+        let escrow = self.counter.escrow;
+        let taker = self.taker.key();
+        let counter_seeds: &[&[&[u8]]] = &[&[b"counter", escrow.as_ref(), taker.as_ref(), &[self.counter.bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.counter_vault.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.taker_ata_b.to_account_info(),
+            authority: self.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, counter_seeds);
+        transfer_checked(cpi_ctx, self.counter_vault.amount, self.mint_b.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.counter_vault.to_account_info(),
+            destination: self.taker.to_account_info(),
+            authority: self.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, counter_seeds);
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+}
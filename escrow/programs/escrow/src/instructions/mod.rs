@@ -11,4 +11,40 @@ pub mod take;
 pub use take::*;
 
 pub mod refund;
-pub use refund::*;
\ No newline at end of file
+pub use refund::*;
+
+pub mod expire;
+pub use expire::*;
+
+pub mod bundle_escrow;
+pub use bundle_escrow::*;
+
+pub mod make_bundle;
+pub use make_bundle::*;
+
+pub mod take_bundle;
+pub use take_bundle::*;
+
+pub mod refund_bundle;
+pub use refund_bundle::*;
+
+pub mod amend_offer;
+pub use amend_offer::*;
+
+pub mod counter;
+pub use counter::*;
+
+pub mod make_counter;
+pub use make_counter::*;
+
+pub mod accept_counter;
+pub use accept_counter::*;
+
+pub mod order_book;
+pub use order_book::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod initialize_config;
+pub use initialize_config::*;
\ No newline at end of file
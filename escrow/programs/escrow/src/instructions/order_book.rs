@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_BOOK_PAGE_LEN;
+
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub page: u32,
+    #[max_len(MAX_BOOK_PAGE_LEN)]
+    pub escrows: Vec<Pubkey>, // open offers selling mint_a for mint_b
+    pub bump: u8,
+}
+
+// Pages for a mint pair are created in order, so clients can walk 0..pages.
+#[account]
+#[derive(InitSpace)]
+pub struct BookIndex {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub pages: u32,
+    pub bump: u8,
+}
+
+impl OrderBook {
+    pub fn remove(&mut self, escrow: &Pubkey) {
+        self.escrows.retain(|key| key != escrow);
+    }
+}
@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{TransferChecked, transfer_checked, close_account, CloseAccount , Token, Mint, TokenAccount};
 
+use crate::error::EscrowError;
 use crate::instructions::escrow::Escrow;
+use crate::instructions::order_book::OrderBook;
 
 #[derive(Accounts)]
 pub struct Refund<'info> {
@@ -19,12 +21,14 @@ pub struct Refund<'info> {
         associated_token::authority = maker,
         associated_token::token_program = token_program,
     )]
-    pub maker_ata_a: Account<'info, TokenAccount>,  
+    pub maker_ata_a: Option<Account<'info, TokenAccount>>,  
 
     #[account(
         mut,
         close = maker,
         has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        has_one = mint_b @ EscrowError::MintMismatch,
         seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],  
         bump = escrow.bump,
     )]
@@ -36,7 +40,14 @@ pub struct Refund<'info> {
         associated_token::authority = escrow,
         associated_token::token_program = token_program,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: Option<Account<'info, TokenAccount>>, // omitted when mint A is native
+
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref(), escrow.book_page.to_le_bytes().as_ref()],
+        bump = book.bump,
+    )]
+    pub book: Account<'info, OrderBook>,
     
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -45,12 +56,21 @@ pub struct Refund<'info> {
 
 impl<'info> Refund<'info> {
     pub fn refund(&mut self) -> Result<()> {
+        // Native deposits sit in the escrow's own lamports and go back with `close = maker`.
+        if Escrow::is_native(&self.mint_a.key()) {
+            return Ok(());
+        }
+
+        let (Some(vault), Some(maker_ata_a)) = (&self.vault, &self.maker_ata_a) else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+
         let cpi_program = self.token_program.to_account_info();
         
         let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
+            from: vault.to_account_info(),
             mint: self.mint_a.to_account_info(),
-            to: self.maker_ata_a.to_account_info(),
+            to: maker_ata_a.to_account_info(),
             authority: self.escrow.to_account_info(), 
         };
 
@@ -60,30 +80,34 @@ impl<'info> Refund<'info> {
             b"escrow", 
             self.maker.key.as_ref(),
             escrow_seed.as_ref(),
-            &[self.escrow.bump as u8],
+            &[self.escrow.bump],
         ];
 
         let signer_seeds = &[&seeds[..]];
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        transfer_checked(cpi_ctx, self.vault.amount, self.mint_a.decimals)?;
+        transfer_checked(cpi_ctx, vault.amount, self.mint_a.decimals)?;
 
         Ok(())
     }
 
     pub fn close(&mut self) -> Result<()> {
-        let cpi_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.maker.to_account_info(),
-            authority: self.escrow.to_account_info(),
-        };
-
-        let seed_bytes = self.escrow.seed.to_le_bytes();
-        let seeds = &[b"escrow", self.escrow.maker.as_ref(), seed_bytes.as_ref(), &[self.escrow.bump as u8]];
-        let signers_seeds = [&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signers_seeds);
-
-        close_account(cpi_ctx)?;
+        if let Some(vault) = &self.vault {
+            let cpi_accounts = CloseAccount {
+                account: vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let seed_bytes = self.escrow.seed.to_le_bytes();
+            let seeds = &[b"escrow", self.escrow.maker.as_ref(), seed_bytes.as_ref(), &[self.escrow.bump]];
+            let signers_seeds = [&seeds[..]];
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, &signers_seeds);
+
+            close_account(cpi_ctx)?;
+        }
+
+        self.book.remove(&self.escrow.key());
 
         Ok(())
     }
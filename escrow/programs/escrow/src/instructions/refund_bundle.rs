@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::error::EscrowError;
+use crate::instructions::bundle_escrow::{release_leg, BundleEscrow};
+
+#[derive(Accounts)]
+pub struct RefundBundle<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"bundle", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, BundleEscrow>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RefundBundle<'info> {
+    // Remaining accounts: [mint, vault, maker_ata] for every offered leg.
+    pub fn refund(&mut self, remaining: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            remaining.len() == self.escrow.offered.len() * 3,
+            EscrowError::InvalidBundleAccounts
+        );
+
+        for (leg, accounts) in self.escrow.offered.iter().zip(remaining.chunks(3)) {
+            let (mint, vault, maker_ata) = (&accounts[0], &accounts[1], &accounts[2]);
+
+            release_leg(
+                &self.escrow,
+                leg,
+                mint,
+                vault,
+                maker_ata.clone(),
+                maker_ata.clone(),
+                self.maker.to_account_info(),
+                &self.token_program,
+            )?;
+        }
+
+        Ok(())
+    }
+}
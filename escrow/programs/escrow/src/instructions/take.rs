@@ -1,14 +1,19 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
+use crate::error::EscrowError;
+use crate::instructions::config::Config;
 use crate::instructions::escrow::Escrow;
+use crate::instructions::order_book::OrderBook;
 
 #[derive(Accounts)]
 pub struct Take<'info> {
     #[account(mut)]
     pub taker: Signer<'info>,
 
+    #[account(mut)]
     pub maker: SystemAccount<'info>,
 
     #[account(
@@ -22,13 +27,13 @@ pub struct Take<'info> {
     pub mint_b: Account<'info, Mint>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = taker,
         associated_token::mint = mint_a,
         associated_token::authority = taker,
         associated_token::token_program = token_program,
     )]
-    pub taker_ata_a: Account<'info, TokenAccount>,
+    pub taker_ata_a: Option<Account<'info, TokenAccount>>,
 
     #[account(
         mut, 
@@ -36,32 +41,63 @@ pub struct Take<'info> {
         associated_token::authority = taker,
         associated_token::token_program = token_program,
     )]
-    pub taker_ata_b: Account<'info, TokenAccount>,
+    pub taker_ata_b: Option<Account<'info, TokenAccount>>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = taker,
         associated_token::mint = mint_b, 
         associated_token::authority = maker,
         associated_token::token_program = token_program,
     )] 
-    pub maker_ata_b: Account<'info, TokenAccount>, 
+    pub maker_ata_b: Option<Account<'info, TokenAccount>>, 
 
     #[account(
         mut,
-        seeds = [b"escrow", maker.key().as_ref()],
-        bump,
-        close = maker,
+        has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        has_one = mint_b @ EscrowError::MintMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.taker.is_none() || escrow.taker == Some(taker.key()) @ EscrowError::UnauthorizedTaker,
     )]
     pub escrow: Account<'info, Escrow>,
 
     #[account(
         mut,
         associated_token::mint = mint_a,
-        associated_token::authority = maker,
-        close = maker
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>, // omitted when mint A is native
+
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref(), escrow.book_page.to_le_bytes().as_ref()],
+        bump = book.bump,
+    )]
+    pub book: Account<'info, OrderBook>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.treasury,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Account<'info, TokenAccount>>, // omitted when mint B is native or no fee is due
 
     pub token_program: Program<'info, Token>,
 
@@ -71,71 +107,185 @@ pub struct Take<'info> {
 }
 
 impl<'info> Take<'info> {
-    pub fn deposit(&mut self) -> Result<()> {
+    pub fn fill(&mut self, amount_b: u64, expected_deposit: u64, expected_receive: u64) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.escrow.expires_at,
+            EscrowError::OfferExpired
+        );
+        // The maker can amend at any time, so the taker pins the terms they saw.
+        require!(
+            self.escrow.deposit == expected_deposit && self.escrow.receive == expected_receive,
+            EscrowError::OfferTermsChanged
+        );
+        require!(
+            amount_b > 0 && amount_b <= self.escrow.receive,
+            EscrowError::InvalidFillAmount
+        );
+
+        // Rounds down, so the maker never releases more than the agreed rate.
+        let closing = amount_b == self.escrow.receive;
+        let amount_a = if closing {
+            self.escrow.deposit
+        } else {
+            let amount_a = (self.escrow.deposit as u128)
+                .checked_mul(amount_b as u128)
+                .ok_or(EscrowError::MathOverflow)?
+                .checked_div(self.escrow.receive as u128)
+                .ok_or(EscrowError::MathOverflow)?;
+            u64::try_from(amount_a).map_err(|_| EscrowError::MathOverflow)?
+        };
+        require!(amount_a > 0, EscrowError::FillTooSmall);
+
+        self.deposit(amount_b)?;
+
+        // The closing fill sweeps the vault, so tokens donated to it can't block the close.
+        let released = match &self.vault {
+            Some(vault) if closing && !Escrow::is_native(&self.mint_a.key()) => vault.amount,
+            _ => amount_a,
+        };
+        self.release(released)?;
+
+        self.escrow.receive -= amount_b;
+        self.escrow.deposit -= amount_a;
+
+        if closing {
+            self.book.remove(&self.escrow.key());
+            self.close()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amount_b: u64) -> Result<()> {
+        let fee = self.config.fee(amount_b);
+
+        if Escrow::is_native(&self.mint_b.key()) {
+            if fee > 0 {
+                self.pay_lamports(&self.treasury.to_account_info(), fee)?;
+            }
+            return self.pay_lamports(&self.maker.to_account_info(), amount_b - fee);
+        }
+
+        let Some(maker_ata_b) = &self.maker_ata_b else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+
+        if fee > 0 {
+            let Some(treasury_ata_b) = &self.treasury_ata_b else {
+                return err!(EscrowError::MissingTokenAccount);
+            };
+            self.pay_tokens(treasury_ata_b, fee)?;
+        }
+        self.pay_tokens(maker_ata_b, amount_b - fee)
+    }
+
+    fn pay_lamports(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: to.clone(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, amount)
+    }
+
+    fn pay_tokens(&self, to: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        let Some(taker_ata_b) = &self.taker_ata_b else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
-            from: self.taker_ata_a.to_account_info(),
-            to: self.maker_ata_b.to_account_info(),
+            from: taker_ata_b.to_account_info(),
+            to: to.to_account_info(),
             mint: self.mint_b.to_account_info(),
             authority: self.taker.to_account_info(),
         };
 
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)?;
-
-        Ok(())
+        transfer_checked(cpi_ctx, amount, self.mint_b.decimals)
     }
 
-    pub fn release(&mut self) -> Result<()> {
+    pub fn release(&mut self, amount_a: u64) -> Result<()> {
+        if Escrow::is_native(&self.mint_a.key()) {
+            let escrow_info = self.escrow.to_account_info();
+            let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+            require!(
+                escrow_info.lamports().saturating_sub(rent) >= amount_a,
+                EscrowError::InsufficientVaultBalance
+            );
+
+            // The escrow is owned by this program, so its lamports are debited directly.
+            **self.escrow.to_account_info().try_borrow_mut_lamports()? -= amount_a;
+            **self.taker.to_account_info().try_borrow_mut_lamports()? += amount_a;
+            return Ok(());
+        }
+
+        let (Some(vault), Some(taker_ata_a)) = (&self.vault, &self.taker_ata_a) else {
+            return err!(EscrowError::MissingTokenAccount);
+        };
+        require!(vault.amount >= amount_a, EscrowError::InsufficientVaultBalance);
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.taker_ata_a.to_account_info(),
+            from: vault.to_account_info(),
+            to: taker_ata_a.to_account_info(),
             mint: self.mint_a.to_account_info(),
             authority: self.escrow.to_account_info(),
         };
 
         let maker = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
         let escrow_seeds: &[&[&[u8]]] = &[
             &[
                 b"escrow",
                 maker.as_ref(),
-                &[self.escrow.bump as u8]
+                seed.as_ref(),
+                &[self.escrow.bump]
             ]
         ];
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, escrow_seeds);
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_a.decimals)?;
+        transfer_checked(cpi_ctx, amount_a, self.mint_a.decimals)?;
 
         Ok(())
     }
 
     pub fn close(&mut self) -> Result<()> {
+        if let Some(vault) = &self.vault {
+            self.close_vault(vault)?;
+        }
+
+        self.escrow.close(self.maker.to_account_info())?;
+
+        Ok(())
+    }
+
+    fn close_vault(&self, vault: &Account<'info, TokenAccount>) -> Result<()> {
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.taker.to_account_info(),
+            account: vault.to_account_info(),
+            destination: self.maker.to_account_info(),
             authority: self.escrow.to_account_info(),
         };
 
         let maker = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
         let escrow_seeds: &[&[&[u8]]] = &[
             &[
                 b"escrow",
                 maker.as_ref(),
-                &[self.escrow.bump as u8]
+                seed.as_ref(),
+                &[self.escrow.bump]
             ]
         ];
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, escrow_seeds);
 
-        close_account(cpi_ctx)?;
-
-        Ok(())
+        close_account(cpi_ctx)
     }
-}
\ No newline at end of file
+}
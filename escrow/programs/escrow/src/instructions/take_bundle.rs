@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::{create_idempotent, AssociatedToken, Create};
+use anchor_spl::token::{transfer_checked, Mint, Token, TransferChecked};
+
+use crate::error::EscrowError;
+use crate::instructions::config::Config;
+use crate::instructions::bundle_escrow::{release_leg, BundleEscrow};
+
+#[derive(Accounts)]
+pub struct TakeBundle<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"bundle", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.taker.is_none() || escrow.taker == Some(taker.key()) @ EscrowError::UnauthorizedTaker,
+    )]
+    pub escrow: Account<'info, BundleEscrow>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.treasury,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Remaining accounts: [mint, vault, taker_ata, maker_ata] for every offered leg,
+// followed by [mint, taker_ata, maker_ata, treasury_ata] for every requested leg.
+impl<'info> TakeBundle<'info> {
+    pub fn pay(&mut self, remaining: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.escrow.expires_at,
+            EscrowError::OfferExpired
+        );
+
+        let offered_accounts = self.escrow.offered.len() * 4;
+        require!(
+            remaining.len() == offered_accounts + self.escrow.requested.len() * 4,
+            EscrowError::InvalidBundleAccounts
+        );
+
+        for (leg, accounts) in self.escrow.requested.iter().zip(remaining[offered_accounts..].chunks(4)) {
+            let (mint, taker_ata, maker_ata, treasury_ata) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+            let mint_account = Account::<Mint>::try_from(mint)?;
+            require_keys_eq!(mint.key(), leg.mint, EscrowError::InvalidBundleAccounts);
+
+            // The ATA program rejects maker_ata unless it is the maker's ATA for this mint.
+            let create_accounts = Create {
+                payer: self.taker.to_account_info(),
+                associated_token: maker_ata.clone(),
+                authority: self.maker.to_account_info(),
+                mint: mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            };
+            create_idempotent(CpiContext::new(self.associated_token_program.to_account_info(), create_accounts))?;
+
+            let fee = self.config.fee(leg.amount);
+            if fee > 0 {
+                let create_accounts = Create {
+                    payer: self.taker.to_account_info(),
+                    associated_token: treasury_ata.clone(),
+                    authority: self.treasury.to_account_info(),
+                    mint: mint.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                };
+                create_idempotent(CpiContext::new(self.associated_token_program.to_account_info(), create_accounts))?;
+
+                let transfer_accounts = TransferChecked {
+                    from: taker_ata.clone(),
+                    mint: mint.clone(),
+                    to: treasury_ata.clone(),
+                    authority: self.taker.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+                transfer_checked(cpi_ctx, fee, mint_account.decimals)?;
+            }
+
+            let transfer_accounts = TransferChecked {
+                from: taker_ata.clone(),
+                mint: mint.clone(),
+                to: maker_ata.clone(),
+                authority: self.taker.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+            transfer_checked(cpi_ctx, leg.amount - fee, mint_account.decimals)?;
+        }
+
+        if self.escrow.requested_lamports > 0 {
+            let fee = self.config.fee(self.escrow.requested_lamports);
+            if fee > 0 {
+                let cpi_accounts = Transfer {
+                    from: self.taker.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                transfer(cpi_ctx, fee)?;
+            }
+
+            let cpi_accounts = Transfer {
+                from: self.taker.to_account_info(),
+                to: self.maker.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+            transfer(cpi_ctx, self.escrow.requested_lamports - fee)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn release(&mut self, remaining: &'info [AccountInfo<'info>]) -> Result<()> {
+        for (leg, accounts) in self.escrow.offered.iter().zip(remaining.chunks(4)) {
+            let (mint, vault, taker_ata, maker_ata) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+            let create_accounts = Create {
+                payer: self.taker.to_account_info(),
+                associated_token: taker_ata.clone(),
+                authority: self.taker.to_account_info(),
+                mint: mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            };
+            create_idempotent(CpiContext::new(self.associated_token_program.to_account_info(), create_accounts))?;
+
+            // Receives any dust donated to the vault; the ATA program pins it to the maker.
+            let create_accounts = Create {
+                payer: self.taker.to_account_info(),
+                associated_token: maker_ata.clone(),
+                authority: self.maker.to_account_info(),
+                mint: mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            };
+            create_idempotent(CpiContext::new(self.associated_token_program.to_account_info(), create_accounts))?;
+
+            release_leg(
+                &self.escrow,
+                leg,
+                mint,
+                vault,
+                taker_ata.clone(),
+                maker_ata.clone(),
+                self.maker.to_account_info(),
+                &self.token_program,
+            )?;
+        }
+
+        Ok(())
+    }
+}
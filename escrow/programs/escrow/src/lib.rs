@@ -0,0 +1,95 @@
+#![allow(unexpected_cfgs)]
+pub mod constants;
+pub mod error;
+pub mod instructions;
+
+use anchor_lang::prelude::*;
+
+pub use constants::*;
+pub use instructions::*;
+
+declare_id!("2m3HqaV8HghNRkBqzgkAJchrpEdH1P42wQwQbyf5W723");
+
+#[program]
+pub mod escrow {
+    use super::*;
+
+    pub fn make(ctx: Context<Make>, seed: u64, deposit_amount: u64, receive_amount: u64, expires_at: i64, taker: Option<Pubkey>, book_page: u32) -> Result<()> {
+        ctx.accounts.make_offer(seed, deposit_amount, receive_amount, expires_at, taker, book_page, &ctx.bumps)?;
+        ctx.accounts.deposit(deposit_amount)?;
+        ctx.accounts.list(book_page, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        ctx.accounts.refund()?;
+        ctx.accounts.close()?;
+        Ok(())
+    }
+
+    pub fn expire(ctx: Context<Expire>) -> Result<()> {
+        ctx.accounts.refund()?;
+        ctx.accounts.close()?;
+        Ok(())
+    }
+
+    pub fn amend_offer(ctx: Context<AmendOffer>, deposit_amount: u64, receive_amount: u64) -> Result<()> {
+        ctx.accounts.amend_offer(deposit_amount, receive_amount)?;
+        Ok(())
+    }
+
+    pub fn counter_offer(ctx: Context<MakeCounter>, receive_amount: u64) -> Result<()> {
+        ctx.accounts.make_counter(receive_amount, &ctx.bumps)?;
+        ctx.accounts.deposit()?;
+        Ok(())
+    }
+
+    pub fn accept_counter(ctx: Context<AcceptCounter>) -> Result<()> {
+        ctx.accounts.pay_maker()?;
+        ctx.accounts.release()?;
+        Ok(())
+    }
+
+    pub fn cancel_counter(ctx: Context<CancelCounter>) -> Result<()> {
+        ctx.accounts.refund()?;
+        Ok(())
+    }
+
+    pub fn make_bundle<'info>(ctx: Context<'_, '_, 'info, 'info, MakeBundle<'info>>, seed: u64, offered: Vec<Leg>, requested: Vec<Leg>, requested_lamports: u64, expires_at: i64, taker: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.make_bundle(seed, offered, requested, requested_lamports, expires_at, taker, &ctx.bumps)?;
+        ctx.accounts.deposit(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn take_bundle<'info>(ctx: Context<'_, '_, 'info, 'info, TakeBundle<'info>>) -> Result<()> {
+        ctx.accounts.pay(ctx.remaining_accounts)?;
+        ctx.accounts.release(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn refund_bundle<'info>(ctx: Context<'_, '_, 'info, 'info, RefundBundle<'info>>) -> Result<()> {
+        ctx.accounts.refund(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.initialize_config(fee_bps, treasury, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, fee_bps: u16, treasury: Pubkey, new_admin: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.update_config(fee_bps, treasury, new_admin)?;
+        Ok(())
+    }
+
+    pub fn take(ctx: Context<Take>, expected_deposit: u64, expected_receive: u64) -> Result<()> {
+        let amount_b = ctx.accounts.escrow.receive;
+        ctx.accounts.fill(amount_b, expected_deposit, expected_receive)?;
+        Ok(())
+    }
+
+    pub fn take_partial(ctx: Context<Take>, amount_b: u64, expected_deposit: u64, expected_receive: u64) -> Result<()> {
+        ctx.accounts.fill(amount_b, expected_deposit, expected_receive)?;
+        Ok(())
+    }
+}
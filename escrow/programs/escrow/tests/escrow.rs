@@ -0,0 +1,411 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, system_instruction},
+    system_program, AccountSerialize, InstructionData, ToAccountMetas,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account};
+
+const DECIMALS: u8 = 6;
+const ONE: u64 = 1_000_000;
+
+struct Test {
+    ctx: ProgramTestContext,
+    maker: Keypair,
+    taker: Keypair,
+    treasury: Pubkey,
+    fee_bps: u16,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+}
+
+// Anchor's entrypoint ties the account slice to the `AccountInfo` lifetime, which the
+// native processor signature does not; the leaked copy shares the same account data.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    escrow::entry(program_id, accounts, data)
+}
+
+async fn send(ctx: &mut ProgramTestContext, ixs: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Result<(), BanksClientError> {
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), signers, ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+async fn setup(fee_bps: u16) -> Test {
+    let maker = Keypair::new();
+    let taker = Keypair::new();
+    let treasury = Keypair::new().pubkey();
+
+    let mut program_test = ProgramTest::new("escrow", escrow::ID, processor!(process_instruction));
+    for key in [maker.pubkey(), taker.pubkey(), treasury] {
+        program_test.add_account(key, Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program::ID));
+    }
+
+    // The config is normally created by the upgrade authority, which a builtin program does not have.
+    let (config, bump) = Pubkey::find_program_address(&[b"config"], &escrow::ID);
+    let mut data = Vec::new();
+    escrow::Config { admin: maker.pubkey(), fee_bps, treasury, bump }.try_serialize(&mut data).unwrap();
+    let mut account = Account::new(LAMPORTS_PER_SOL, data.len(), &escrow::ID);
+    account.data = data;
+    program_test.add_account(config, account);
+
+    let mut ctx = program_test.start_with_context().await;
+    let mint_a = create_mint(&mut ctx, &maker).await;
+    let mint_b = create_mint(&mut ctx, &maker).await;
+    create_funded_ata(&mut ctx, &maker, &maker.pubkey(), &mint_a, 100 * ONE).await;
+    create_funded_ata(&mut ctx, &maker, &taker.pubkey(), &mint_b, 100 * ONE).await;
+
+    Test { ctx, maker, taker, treasury, fee_bps, mint_a, mint_b }
+}
+
+async fn create_mint(ctx: &mut ProgramTestContext, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Mint::LEN);
+    send(
+        ctx,
+        &[
+            system_instruction::create_account(
+                &authority.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &authority.pubkey(), None, DECIMALS).unwrap(),
+        ],
+        authority,
+        &[authority, &mint],
+    )
+    .await
+    .unwrap();
+    mint.pubkey()
+}
+
+async fn create_funded_ata(ctx: &mut ProgramTestContext, payer: &Keypair, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+    let ata = get_associated_token_address(owner, mint);
+    send(
+        ctx,
+        &[
+            create_associated_token_account(&payer.pubkey(), owner, mint, &spl_token::ID),
+            spl_token::instruction::mint_to(&spl_token::ID, mint, &ata, &payer.pubkey(), &[], amount).unwrap(),
+        ],
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+    ata
+}
+
+async fn token_balance(ctx: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = ctx.banks_client.get_account(*account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn is_closed(ctx: &mut ProgramTestContext, account: &Pubkey) -> bool {
+    ctx.banks_client.get_account(*account).await.unwrap().is_none_or(|a| a.lamports == 0)
+}
+
+// Native offers hold their deposit in the escrow's lamports rather than a vault ATA.
+fn native(t: &Test) -> bool {
+    t.mint_a == spl_token::native_mint::ID
+}
+
+async fn lamports(ctx: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    ctx.banks_client.get_account(*account).await.unwrap().map_or(0, |a| a.lamports)
+}
+
+fn escrow_pda(maker: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", maker.as_ref(), seed.to_le_bytes().as_ref()], &escrow::ID).0
+}
+
+fn book_pda(mint_a: &Pubkey, mint_b: &Pubkey, page: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"book", mint_a.as_ref(), mint_b.as_ref(), page.to_le_bytes().as_ref()], &escrow::ID).0
+}
+
+fn make_ix(t: &Test, seed: u64, deposit_amount: u64, receive_amount: u64, expires_at: i64) -> Instruction {
+    let escrow = escrow_pda(&t.maker.pubkey(), seed);
+    let (book_index, _) = Pubkey::find_program_address(&[b"book_index", t.mint_a.as_ref(), t.mint_b.as_ref()], &escrow::ID);
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::Make {
+            maker: t.maker.pubkey(),
+            mint_a: t.mint_a,
+            mint_b: t.mint_b,
+            maker_ata_a: (!native(t)).then(|| get_associated_token_address(&t.maker.pubkey(), &t.mint_a)),
+            escrow,
+            vault: (!native(t)).then(|| get_associated_token_address(&escrow, &t.mint_a)),
+            book_index,
+            book: book_pda(&t.mint_a, &t.mint_b, 0),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::Make { seed, deposit_amount, receive_amount, expires_at, taker: None, book_page: 0 }.data(),
+    }
+}
+
+// Takes the whole offer when `amount_b` is None; `terms` are the deposit and receive the taker quoted.
+fn take_ix(t: &Test, seed: u64, amount_b: Option<u64>, terms: (u64, u64)) -> Instruction {
+    let (maker, taker) = (t.maker.pubkey(), t.taker.pubkey());
+    let escrow = escrow_pda(&maker, seed);
+    let (expected_deposit, expected_receive) = terms;
+    let data = match amount_b {
+        Some(amount_b) => escrow::instruction::TakePartial { amount_b, expected_deposit, expected_receive }.data(),
+        None => escrow::instruction::Take { expected_deposit, expected_receive }.data(),
+    };
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::Take {
+            taker,
+            maker,
+            mint_a: t.mint_a,
+            mint_b: t.mint_b,
+            taker_ata_a: (!native(t)).then(|| get_associated_token_address(&taker, &t.mint_a)),
+            taker_ata_b: Some(get_associated_token_address(&taker, &t.mint_b)),
+            maker_ata_b: Some(get_associated_token_address(&maker, &t.mint_b)),
+            escrow,
+            vault: (!native(t)).then(|| get_associated_token_address(&escrow, &t.mint_a)),
+            book: book_pda(&t.mint_a, &t.mint_b, 0),
+            config: Pubkey::find_program_address(&[b"config"], &escrow::ID).0,
+            treasury: t.treasury,
+            treasury_ata_b: (t.fee_bps > 0).then(|| get_associated_token_address(&t.treasury, &t.mint_b)),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+fn amend_ix(t: &Test, seed: u64, deposit_amount: u64, receive_amount: u64) -> Instruction {
+    let escrow = escrow_pda(&t.maker.pubkey(), seed);
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::AmendOffer {
+            maker: t.maker.pubkey(),
+            mint_a: t.mint_a,
+            maker_ata_a: get_associated_token_address(&t.maker.pubkey(), &t.mint_a),
+            escrow,
+            vault: get_associated_token_address(&escrow, &t.mint_a),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::AmendOffer { deposit_amount, receive_amount }.data(),
+    }
+}
+
+fn expire_ix(t: &Test, cranker: &Pubkey, seed: u64) -> Instruction {
+    let escrow = escrow_pda(&t.maker.pubkey(), seed);
+    Instruction {
+        program_id: escrow::ID,
+        accounts: escrow::accounts::Expire {
+            cranker: *cranker,
+            maker: t.maker.pubkey(),
+            mint_a: t.mint_a,
+            maker_ata_a: (!native(t)).then(|| get_associated_token_address(&t.maker.pubkey(), &t.mint_a)),
+            escrow,
+            vault: (!native(t)).then(|| get_associated_token_address(&escrow, &t.mint_a)),
+            book: book_pda(&t.mint_a, &t.mint_b, 0),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+        }
+        .to_account_metas(None),
+        data: escrow::instruction::Expire {}.data(),
+    }
+}
+
+async fn expiry(ctx: &mut ProgramTestContext) -> i64 {
+    ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp + 3_600
+}
+
+#[tokio::test]
+async fn take_sweeps_dust_donated_to_the_vault() {
+    let mut t = setup(0).await;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let make = make_ix(&t, 1, 10 * ONE, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    let escrow = escrow_pda(&maker.pubkey(), 1);
+    let vault = get_associated_token_address(&escrow, &t.mint_a);
+    let donation = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &get_associated_token_address(&maker.pubkey(), &t.mint_a),
+        &vault,
+        &maker.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    send(&mut t.ctx, &[donation], &maker, &[&maker]).await.unwrap();
+
+    let taker = t.taker.insecure_clone();
+    let take = take_ix(&t, 1, None, (10 * ONE, 20 * ONE));
+    send(&mut t.ctx, &[take], &taker, &[&taker]).await.unwrap();
+
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_a)).await, 10 * ONE + 1);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&maker.pubkey(), &t.mint_b)).await, 20 * ONE);
+    assert!(is_closed(&mut t.ctx, &vault).await);
+    assert!(is_closed(&mut t.ctx, &escrow).await);
+}
+
+#[tokio::test]
+async fn take_rejects_terms_amended_after_the_quote() {
+    let mut t = setup(0).await;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let make = make_ix(&t, 1, 10 * ONE, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    // The maker front-runs the taker by shrinking the deposit.
+    let amend = amend_ix(&t, 1, ONE, 20 * ONE);
+    send(&mut t.ctx, &[amend], &maker, &[&maker]).await.unwrap();
+
+    let taker = t.taker.insecure_clone();
+    let stale_take = take_ix(&t, 1, None, (10 * ONE, 20 * ONE));
+    assert!(send(&mut t.ctx, &[stale_take], &taker, &[&taker]).await.is_err());
+    let stale_partial = take_ix(&t, 1, Some(10 * ONE), (10 * ONE, 20 * ONE));
+    assert!(send(&mut t.ctx, &[stale_partial], &taker, &[&taker]).await.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_b)).await, 100 * ONE);
+
+    // Quoting the amended terms goes through.
+    let take = take_ix(&t, 1, None, (ONE, 20 * ONE));
+    send(&mut t.ctx, &[take], &taker, &[&taker]).await.unwrap();
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_a)).await, ONE);
+}
+
+#[tokio::test]
+async fn make_accepts_a_maker_who_already_holds_mint_b() {
+    let mut t = setup(0).await;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let maker_ata_b = create_funded_ata(&mut t.ctx, &maker, &maker.pubkey(), &t.mint_b, ONE).await;
+
+    let make = make_ix(&t, 1, 10 * ONE, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    let taker = t.taker.insecure_clone();
+    let take = take_ix(&t, 1, None, (10 * ONE, 20 * ONE));
+    send(&mut t.ctx, &[take], &taker, &[&taker]).await.unwrap();
+    assert_eq!(token_balance(&mut t.ctx, &maker_ata_b).await, 21 * ONE);
+}
+
+#[tokio::test]
+async fn take_splits_the_fee_with_the_treasury() {
+    let mut t = setup(100).await;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let make = make_ix(&t, 1, 10 * ONE, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    let taker = t.taker.insecure_clone();
+    let take = take_ix(&t, 1, None, (10 * ONE, 20 * ONE));
+    send(&mut t.ctx, &[take], &taker, &[&taker]).await.unwrap();
+
+    // 1% of the 20 tokens goes to the treasury, the rest to the maker.
+    let treasury = t.treasury;
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&treasury, &t.mint_b)).await, ONE / 5);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&maker.pubkey(), &t.mint_b)).await, 20 * ONE - ONE / 5);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_b)).await, 80 * ONE);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_a)).await, 10 * ONE);
+}
+
+#[tokio::test]
+async fn partial_fills_round_in_the_makers_favour() {
+    let mut t = setup(100).await;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let taker = t.taker.insecure_clone();
+    let make = make_ix(&t, 1, 10, 300, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    // 150 of 300 releases exactly half; the 1.5 unit fee rounds down to 1.
+    let half = take_ix(&t, 1, Some(150), (10, 300));
+    send(&mut t.ctx, &[half], &taker, &[&taker]).await.unwrap();
+    // 31 of the remaining 150 is worth 1.03 units of A and owes a 0.31 unit fee, both rounded down.
+    let sliver = take_ix(&t, 1, Some(31), (5, 150));
+    send(&mut t.ctx, &[sliver], &taker, &[&taker]).await.unwrap();
+    // 29 of the remaining 119 is worth under one unit of A, so it can't be filled.
+    let dust = take_ix(&t, 1, Some(29), (4, 119));
+    assert!(send(&mut t.ctx, &[dust], &taker, &[&taker]).await.is_err());
+    // The closing fill releases whatever is left, so no deposit is stranded by rounding.
+    let rest = take_ix(&t, 1, Some(119), (4, 119));
+    send(&mut t.ctx, &[rest], &taker, &[&taker]).await.unwrap();
+
+    let treasury = t.treasury;
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&taker.pubkey(), &t.mint_a)).await, 10);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&maker.pubkey(), &t.mint_b)).await, 149 + 31 + 118);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&treasury, &t.mint_b)).await, 2);
+    assert!(is_closed(&mut t.ctx, &escrow_pda(&maker.pubkey(), 1)).await);
+}
+
+#[tokio::test]
+async fn native_take_moves_lamports_out_of_the_escrow() {
+    let mut t = setup(0).await;
+    t.mint_a = spl_token::native_mint::ID;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let taker = t.taker.insecure_clone();
+    let make = make_ix(&t, 1, LAMPORTS_PER_SOL, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    let escrow = escrow_pda(&maker.pubkey(), 1);
+    let escrow_lamports = lamports(&mut t.ctx, &escrow).await;
+    assert!(escrow_lamports > LAMPORTS_PER_SOL);
+    // The maker's ATA exists up front so the taker's balance only moves by the release.
+    create_funded_ata(&mut t.ctx, &maker, &maker.pubkey(), &t.mint_b, 0).await;
+    let maker_before = lamports(&mut t.ctx, &maker.pubkey()).await;
+    let taker_before = lamports(&mut t.ctx, &taker.pubkey()).await;
+
+    let payer = t.ctx.payer.insecure_clone();
+    let take = take_ix(&t, 1, None, (LAMPORTS_PER_SOL, 20 * ONE));
+    send(&mut t.ctx, &[take], &payer, &[&payer, &taker]).await.unwrap();
+
+    assert_eq!(lamports(&mut t.ctx, &taker.pubkey()).await, taker_before + LAMPORTS_PER_SOL);
+    assert_eq!(lamports(&mut t.ctx, &maker.pubkey()).await, maker_before + escrow_lamports - LAMPORTS_PER_SOL);
+    assert_eq!(token_balance(&mut t.ctx, &get_associated_token_address(&maker.pubkey(), &t.mint_b)).await, 20 * ONE);
+    assert!(is_closed(&mut t.ctx, &escrow).await);
+}
+
+#[tokio::test]
+async fn native_expire_pays_the_crank_reward() {
+    let mut t = setup(0).await;
+    t.mint_a = spl_token::native_mint::ID;
+    let expires_at = expiry(&mut t.ctx).await;
+    let maker = t.maker.insecure_clone();
+    let make = make_ix(&t, 1, LAMPORTS_PER_SOL, 20 * ONE, expires_at);
+    send(&mut t.ctx, &[make], &maker, &[&maker]).await.unwrap();
+
+    let escrow = escrow_pda(&maker.pubkey(), 1);
+    let escrow_lamports = lamports(&mut t.ctx, &escrow).await;
+    let cranker = t.taker.insecure_clone();
+    let maker_before = lamports(&mut t.ctx, &maker.pubkey()).await;
+    let cranker_before = lamports(&mut t.ctx, &cranker.pubkey()).await;
+
+    let payer = t.ctx.payer.insecure_clone();
+    let early = expire_ix(&t, &cranker.pubkey(), 1);
+    assert!(send(&mut t.ctx, &[early], &payer, &[&payer, &cranker]).await.is_err());
+
+    let mut clock = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = expires_at;
+    t.ctx.set_sysvar(&clock);
+    let expire = expire_ix(&t, &cranker.pubkey(), 1);
+    send(&mut t.ctx, &[expire], &payer, &[&payer, &cranker]).await.unwrap();
+
+    assert_eq!(lamports(&mut t.ctx, &cranker.pubkey()).await, cranker_before + escrow::constants::CRANK_REWARD);
+    assert_eq!(lamports(&mut t.ctx, &maker.pubkey()).await, maker_before + escrow_lamports - escrow::constants::CRANK_REWARD);
+    assert!(is_closed(&mut t.ctx, &escrow).await);
+}
@@ -0,0 +1,15 @@
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+
+pub const MAX_VAULT_MINTS: usize = 16;
+
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+pub const RECOVERY_CHALLENGE_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+// Extensions that let someone other than the vault move, block or hide its balance.
+pub const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+    ExtensionType::ConfidentialTransferMint,
+];
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Insufficient Funds In Vault")]
+    InsufficientFunds,
+    #[msg("Vault Is Still Locked")]
+    VaultLocked,
+    #[msg("Lock Can Only Be Extended")]
+    LockNotExtended,
+    #[msg("Vault Still Holds Tokens")]
+    VaultNotEmpty,
+    #[msg("Vault Registry Is Full")]
+    RegistryFull,
+    #[msg("Vault Balance Would Fall Below Rent Exemption")]
+    BelowRentExemption,
+    #[msg("Allowance Has Expired")]
+    AllowanceExpired,
+    #[msg("Amount Exceeds Allowance")]
+    AllowanceExceeded,
+    #[msg("Invalid Multisig Signers Or Threshold")]
+    InvalidMultisig,
+    #[msg("Multisig Is Already Configured")]
+    MultisigAlreadySet,
+    #[msg("Vault Is Multisig Controlled")]
+    MultisigRequired,
+    #[msg("Vault Is Not Multisig Controlled")]
+    NotMultisig,
+    #[msg("Signer Is Not A Multisig Member")]
+    NotMultisigMember,
+    #[msg("Proposal Already Approved By Signer")]
+    AlreadyApproved,
+    #[msg("Proposal Has Not Reached Threshold")]
+    ThresholdNotMet,
+    #[msg("Mint Has An Unsupported Extension")]
+    UnsupportedMintExtension,
+    #[msg("Invalid Stream Rate Or Schedule")]
+    InvalidStream,
+    #[msg("Nothing Has Vested Since Last Claim")]
+    NothingToClaim,
+    #[msg("Guardian And Backup Must Be Set Together")]
+    InvalidGuardian,
+    #[msg("Signer Is Not The Vault Guardian")]
+    NotGuardian,
+    #[msg("Recovery Already In Progress")]
+    RecoveryPending,
+    #[msg("No Recovery In Progress")]
+    NoRecoveryPending,
+    #[msg("Recovery Challenge Window Has Not Passed")]
+    ChallengeWindowActive,
+    #[msg("Amount Too Small To Mint Or Redeem Shares")]
+    ZeroShares,
+    #[msg("Signer Is Not The Program Upgrade Authority")]
+    NotUpgradeAuthority,
+    #[msg("Deposit Exceeds Per User Cap")]
+    UserCapExceeded,
+    #[msg("Deposit Exceeds Global Cap")]
+    GlobalCapExceeded,
+    #[msg("Depositor Is Not On The Allowlist")]
+    NotAllowlisted,
+    #[msg("Arithmetic Overflow")]
+    MathOverflow,
+    #[msg("Proposal Is For A Different Action")]
+    WrongProposalKind,
+    #[msg("User Position Account Required By The Mint Config")]
+    PositionRequired,
+}
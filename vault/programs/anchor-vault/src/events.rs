@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DepositEvent {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct CloseEvent {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct PoolDepositEvent {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub total_assets: u64,
+}
+
+#[event]
+pub struct PoolWithdrawEvent {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub total_assets: u64,
+}
+
+#[event]
+pub struct PoolDonateEvent {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub total_assets: u64,
+}
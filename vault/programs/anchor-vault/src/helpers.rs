@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::UNSUPPORTED_MINT_EXTENSIONS;
+use crate::error::VaultError;
+use crate::state::{UserPosition, VaultConfig, VaultState};
+
+pub fn assert_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != Token2022::id() {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+
+    for extension in mint_state.get_extension_types()? {
+        require!(
+            !UNSUPPORTED_MINT_EXTENSIONS.contains(&extension),
+            VaultError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+pub fn transfer_from_vault<'info>(
+    vault_state: &VaultState,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        vault_state.owner.as_ref(),
+        vault_state.mint.as_ref(),
+        &[vault_state.vault_bump],
+    ]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to,
+        authority: vault.to_account_info(),
+        mint: mint.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+
+    transfer_checked(cpi_context, amount, mint.decimals)
+}
+
+pub fn close_vault<'info>(
+    vault_state: &VaultState,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        vault_state.owner.as_ref(),
+        vault_state.mint.as_ref(),
+        &[vault_state.vault_bump],
+    ]];
+
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination,
+        authority: vault.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+
+    close_account(cpi_context)
+}
+
+pub fn verify_allowlist(depositor: &Pubkey, proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let mut node = hashv(&[depositor.as_ref()]).0;
+
+    for sibling in proof {
+        node = if node <= *sibling {
+            hashv(&[&node, sibling]).0
+        } else {
+            hashv(&[sibling, &node]).0
+        };
+    }
+
+    node == *root
+}
+
+// Mints without a config place no limits on their vaults.
+fn load_config(config: &AccountInfo) -> Result<Option<VaultConfig>> {
+    if config.data_is_empty() {
+        return Ok(None);
+    }
+
+    require_keys_eq!(*config.owner, crate::ID);
+    Ok(Some(VaultConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?))
+}
+
+// Deposits are counted against a per-user PDA rather than a balance, since shares and
+// withdrawals to other wallets would otherwise reset what the cap sees.
+pub fn enforce_config(
+    config: &AccountInfo,
+    mut position: Option<&mut Account<UserPosition>>,
+    position_bump: Option<u8>,
+    depositor: &Pubkey,
+    proof: &[[u8; 32]],
+    credited: u64,
+) -> Result<()> {
+    if let Some(position) = position.as_deref_mut() {
+        if position.user == Pubkey::default() {
+            position.config = config.key();
+            position.user = *depositor;
+            position.bump = position_bump.ok_or(VaultError::PositionRequired)?;
+        }
+    }
+
+    let Some(mut state) = load_config(config)? else {
+        return Ok(());
+    };
+
+    if let Some(root) = state.allowlist_root {
+        require!(verify_allowlist(depositor, proof, &root), VaultError::NotAllowlisted);
+    }
+
+    let position = position.ok_or(VaultError::PositionRequired)?;
+
+    position.net_deposited = position.net_deposited.checked_add(credited).ok_or(VaultError::MathOverflow)?;
+    if let Some(cap) = state.per_user_cap {
+        require!(position.net_deposited <= cap, VaultError::UserCapExceeded);
+    }
+
+    state.net_deposited = state.net_deposited.checked_add(credited).ok_or(VaultError::MathOverflow)?;
+    if let Some(cap) = state.global_cap {
+        require!(state.net_deposited <= cap, VaultError::GlobalCapExceeded);
+    }
+
+    state.try_serialize(&mut &mut config.try_borrow_mut_data()?[..])
+}
+
+// Frees cap room; deposits made before the config existed were never counted, hence saturating.
+pub fn release_config(config: &AccountInfo, position: Option<&mut Account<UserPosition>>, amount: u64) -> Result<()> {
+    let Some(mut state) = load_config(config)? else {
+        return Ok(());
+    };
+
+    if let Some(position) = position {
+        position.net_deposited = position.net_deposited.saturating_sub(amount);
+    }
+
+    state.net_deposited = state.net_deposited.saturating_sub(amount);
+    state.try_serialize(&mut &mut config.try_borrow_mut_data()?[..])
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+use crate::state::{Proposal, VaultState};
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub signer: Signer<'info>,
+
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        seeds = [b"proposal", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> Approve<'info> {
+    pub fn approve(&mut self) -> Result<()> {
+        let signer = self.signer.key();
+
+        self.vault_state.assert_member(&signer)?;
+        require!(!self.proposal.approvals.contains(&signer), VaultError::AlreadyApproved);
+
+        self.proposal.approvals.push(signer);
+
+        msg!(
+            "Proposal {} has {} of {} approvals",
+            self.proposal.id,
+            self.proposal.approvals.len(),
+            self.vault_state.threshold
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Allowance, VaultState};
+
+#[derive(Accounts)]
+pub struct ApproveDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: only used as the allowance seed, never read or written
+    pub delegate: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [b"allowance", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveDelegate<'info> {
+    pub fn approve_delegate(&mut self, amount: u64, expires_at: i64, bumps: &ApproveDelegateBumps) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+
+        self.allowance.set_inner(Allowance {
+            vault_state: self.vault_state.key(),
+            delegate: self.delegate.key(),
+            amount,
+            expires_at,
+            bump: bumps.allowance,
+        });
+
+        msg!("Approved {} tokens for {} until {}", amount, self.delegate.key(), expires_at);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: only used as the allowance seed, never read or written
+    pub delegate: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"allowance", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+        close = signer,
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::{release_config, transfer_from_vault};
+use crate::state::{Stream, UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        has_one = recipient,
+        seeds = [b"stream", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), recipient.key().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimStream<'info> {
+    pub fn claim_stream(&mut self) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+        self.vault_state.assert_unlocked()?;
+
+        let (amount, claimed_until) = self.stream.vested_since_last_claim(Clock::get()?.unix_timestamp)?;
+        require!(amount > 0, VaultError::NothingToClaim);
+        require!(amount <= self.vault.amount, VaultError::InsufficientFunds);
+
+        transfer_from_vault(
+            &self.vault_state,
+            &self.vault,
+            self.recipient_token_account.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            amount,
+        )?;
+
+        self.stream.last_claimed_at = claimed_until;
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+        self.vault.reload()?;
+
+        emit!(WithdrawEvent {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            amount,
+            remaining: self.vault.amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Streamed {} tokens to {}", amount, self.recipient.key());
+
+        // Hand the rent back to the owner once the schedule is fully paid out.
+        if claimed_until == self.stream.end_time {
+            self.stream.close(self.owner.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::CloseEvent;
+use crate::helpers::close_vault;
+use crate::state::{VaultRegistry, VaultState};
+
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", signer.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+        close = signer,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Close<'info> {
+    pub fn close(&mut self) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+        self.vault_state.assert_unlocked()?;
+        require!(self.vault.amount == 0, VaultError::VaultNotEmpty);
+
+        let signer_key = self.signer.key();
+        let mint_key = self.mint.key();
+
+        close_vault(&self.vault_state, &self.vault, self.signer.to_account_info(), &self.token_program)?;
+
+        self.registry.mints.retain(|mint| *mint != mint_key);
+
+        emit!(CloseEvent {
+            owner: signer_key,
+            mint: mint_key,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Vault account closed successfully!");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::CloseEvent;
+use crate::helpers::release_config;
+use crate::state::{UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct CloseSol<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+        close = signer,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", signer.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: pinned to the SOL config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", System::id().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseSol<'info> {
+    pub fn close_sol(&mut self) -> Result<()> {
+        self.vault_state.assert_unlocked()?;
+
+        // Sweeping the whole balance, rent reserve included, lets the runtime
+        // reclaim the PDA instead of leaving it below rent exemption.
+        let balance = self.vault.lamports();
+
+        if balance > 0 {
+            let signer_key = self.signer.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"sol_vault", signer_key.as_ref(), &[self.vault_state.vault_bump]]];
+
+            let cpi_accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.signer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
+
+            transfer(cpi_ctx, balance)?;
+
+            self.vault_state.record_withdrawal(balance)?;
+            release_config(&self.config, self.user_position.as_mut(), balance)?;
+        }
+
+        emit!(CloseEvent {
+            owner: self.signer.key(),
+            mint: self.vault_state.mint,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("SOL vault closed successfully!");
+        Ok(())
+    }
+}
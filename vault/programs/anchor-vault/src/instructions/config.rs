@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+
+use crate::error::VaultError;
+use crate::program::AnchorVault;
+use crate::state::VaultConfig;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: a token mint, or the system program id to configure SOL vaults
+    #[account(
+        constraint = mint.key() == System::id() || *mint.owner == Token::id() || *mint.owner == Token2022::id(),
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VaultConfig::INIT_SPACE,
+        seeds = [b"config", mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, VaultConfig>,
+
+    #[account(
+        constraint = this_program.programdata_address()? == Some(program_data.key()),
+    )]
+    pub this_program: Program<'info, AnchorVault>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ VaultError::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(&mut self, per_user_cap: Option<u64>, global_cap: Option<u64>, allowlist_root: Option<[u8; 32]>, bumps: &InitializeConfigBumps) -> Result<()> {
+        self.config.set_inner(VaultConfig {
+            admin: self.admin.key(),
+            mint: self.mint.key(),
+            per_user_cap,
+            global_cap,
+            allowlist_root,
+            net_deposited: 0,
+            bump: bumps.config,
+        });
+
+        msg!("Vault config initialized successfully!");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    /// CHECK: only used to derive the config PDA, which pins it via has_one
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = mint,
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, VaultConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, per_user_cap: Option<u64>, global_cap: Option<u64>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        self.config.per_user_cap = per_user_cap;
+        self.config.global_cap = global_cap;
+        self.config.allowlist_root = allowlist_root;
+
+        msg!("Vault config updated successfully!");
+        Ok(())
+    }
+}
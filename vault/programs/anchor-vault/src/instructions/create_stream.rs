@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::{release_config, transfer_from_vault};
+use crate::state::{Stream, UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub recipient: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [b"stream", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStream<'info> {
+    pub fn create_stream(&mut self, rate_per_second: u64, start_time: i64, end_time: i64, bumps: &CreateStreamBumps) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+        require!(rate_per_second > 0 && end_time > start_time, VaultError::InvalidStream);
+
+        // The full schedule must be payable in u64 so claims can never overflow.
+        let duration = end_time.checked_sub(start_time).ok_or(VaultError::InvalidStream)? as u64;
+        require!(duration.checked_mul(rate_per_second).is_some(), VaultError::InvalidStream);
+
+        self.stream.set_inner(Stream {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            rate_per_second,
+            start_time,
+            end_time,
+            last_claimed_at: start_time,
+            bump: bumps.stream,
+        });
+
+        msg!("Streaming {} tokens per second to {}", rate_per_second, self.recipient.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), recipient.key().as_ref()],
+        bump = stream.bump,
+        close = signer,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelStream<'info> {
+    pub fn cancel_stream(&mut self) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+
+        // Whatever has vested belongs to the recipient, so the stream only closes once it is paid in full.
+        let (amount, _) = self.stream.vested_since_last_claim(Clock::get()?.unix_timestamp)?;
+        require!(amount <= self.vault.amount, VaultError::InsufficientFunds);
+
+        if amount > 0 {
+            self.vault_state.assert_unlocked()?;
+
+            transfer_from_vault(
+                &self.vault_state,
+                &self.vault,
+                self.recipient_token_account.to_account_info(),
+                &self.mint,
+                &self.token_program,
+                amount,
+            )?;
+
+            self.vault_state.record_withdrawal(amount)?;
+            release_config(&self.config, self.user_position.as_mut(), amount)?;
+            self.vault.reload()?;
+
+            emit!(WithdrawEvent {
+                owner: self.signer.key(),
+                mint: self.mint.key(),
+                amount,
+                remaining: self.vault.amount,
+                total_withdrawn: self.vault_state.total_withdrawn,
+            });
+        }
+
+        msg!("Stream cancelled, paid {} vested tokens to {}", amount, self.recipient.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::{release_config, transfer_from_vault};
+use crate::state::{Allowance, UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct DelegatedWithdraw<'info> {
+    pub delegate: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = delegate,
+        has_one = vault_state,
+        seeds = [b"allowance", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DelegatedWithdraw<'info> {
+    pub fn delegated_withdraw(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+        self.vault_state.assert_unlocked()?;
+        require!(
+            Clock::get()?.unix_timestamp < self.allowance.expires_at,
+            VaultError::AllowanceExpired
+        );
+        require!(amount <= self.allowance.amount, VaultError::AllowanceExceeded);
+        require!(amount <= self.vault.amount, VaultError::InsufficientFunds);
+
+        transfer_from_vault(
+            &self.vault_state,
+            &self.vault,
+            self.destination.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            amount,
+        )?;
+
+        self.allowance.amount -= amount;
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+        self.vault.reload()?;
+
+        emit!(WithdrawEvent {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            amount,
+            remaining: self.vault.amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Delegate withdrew {} tokens, {} left in allowance", amount, self.allowance.amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::events::PoolDonateEvent;
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct Donate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref(), admin.key().as_ref()],
+        bump = pool.bump,
+        has_one = admin,
+        has_one = mint,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", pool.key().as_ref()],
+        bump = pool.vault_bump,
+        token::mint = mint,
+        token::authority = pool_vault,
+        token::token_program = token_program,
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin,
+        token::token_program = token_program,
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Donate<'info> {
+    pub fn donate(&mut self, amount: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.admin_token_account.to_account_info(),
+            to: self.pool_vault.to_account_info(),
+            authority: self.admin.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.pool_vault.reload()?;
+        emit!(PoolDonateEvent {
+            admin: self.admin.key(),
+            pool: self.pool.key(),
+            amount,
+            total_assets: self.pool_vault.amount,
+        });
+
+        msg!("Donated {} tokens to pool", amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::{release_config, transfer_from_vault};
+use crate::state::{Proposal, ProposalKind, UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: receives the proposal rent, checked against the proposal
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = proposal.destination,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        constraint = proposal.kind == ProposalKind::Withdraw @ VaultError::WrongProposalKind,
+        seeds = [b"proposal", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Execute<'info> {
+    pub fn execute(&mut self) -> Result<()> {
+        self.vault_state.assert_member(&self.signer.key())?;
+        self.vault_state.assert_unlocked()?;
+
+        require!(
+            self.proposal.approvals.len() >= self.vault_state.threshold as usize,
+            VaultError::ThresholdNotMet
+        );
+
+        let amount = self.proposal.amount;
+        require!(amount <= self.vault.amount, VaultError::InsufficientFunds);
+
+        transfer_from_vault(
+            &self.vault_state,
+            &self.vault,
+            self.destination.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            amount,
+        )?;
+
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+        self.vault.reload()?;
+
+        emit!(WithdrawEvent {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            amount,
+            remaining: self.vault.amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Proposal {} executed, withdrew {} tokens", self.proposal.id, amount);
+        Ok(())
+    }
+}
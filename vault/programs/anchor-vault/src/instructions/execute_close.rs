@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::CloseEvent;
+use crate::helpers::close_vault;
+use crate::state::{Proposal, ProposalKind, VaultRegistry, VaultState};
+
+#[derive(Accounts)]
+pub struct ExecuteClose<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: receives the proposal rent, checked against the proposal
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = proposal.destination,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+        close = owner,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        constraint = proposal.kind == ProposalKind::Close @ VaultError::WrongProposalKind,
+        seeds = [b"proposal", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecuteClose<'info> {
+    pub fn execute_close(&mut self) -> Result<()> {
+        self.vault_state.assert_member(&self.signer.key())?;
+        self.vault_state.assert_unlocked()?;
+        require!(self.vault.amount == 0, VaultError::VaultNotEmpty);
+
+        require!(
+            self.proposal.approvals.len() >= self.vault_state.threshold as usize,
+            VaultError::ThresholdNotMet
+        );
+
+        close_vault(&self.vault_state, &self.vault, self.owner.to_account_info(), &self.token_program)?;
+
+        let mint_key = self.mint.key();
+        self.registry.mints.retain(|mint| *mint != mint_key);
+
+        emit!(CloseEvent {
+            owner: self.owner.key(),
+            mint: mint_key,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Proposal {} executed, vault closed", self.proposal.id);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::{release_config, transfer_from_vault};
+use crate::state::{UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = Some(backup_token_account.owner) == vault_state.backup @ VaultError::InvalidGuardian,
+    )]
+    pub backup_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecuteRecovery<'info> {
+    pub fn execute_recovery(&mut self) -> Result<()> {
+        self.vault_state.finish_recovery()?;
+
+        let amount = self.vault.amount;
+
+        transfer_from_vault(
+            &self.vault_state,
+            &self.vault,
+            self.backup_token_account.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            amount,
+        )?;
+
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+        self.vault.reload()?;
+
+        emit!(WithdrawEvent {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            amount,
+            remaining: self.vault.amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Recovered {} tokens to backup", amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::error::VaultError;
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    pub signer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> ExtendLock<'info> {
+    pub fn extend_lock(&mut self, unlock_at: i64) -> Result<()> {
+        self.vault_state.assert_single_owner()?;
+        require!(unlock_at > self.vault_state.unlock_at, VaultError::LockNotExtended);
+
+        self.vault_state.unlock_at = unlock_at;
+
+        msg!("Vault locked until {}", unlock_at);
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::MAX_VAULT_MINTS;
+use crate::error::VaultError;
+use crate::helpers::assert_supported_mint;
+use crate::state::{VaultRegistry, VaultState};
+
+#[derive(Accounts)]
+pub struct Initialize<'info>{
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + VaultRegistry::INIT_SPACE,
+        seeds = [b"registry", signer.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"vault", signer.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(&mut self, unlock_at: i64, bump: &InitializeBumps) -> Result<()> {
+        assert_supported_mint(&self.mint)?;
+        require!(self.registry.mints.len() < MAX_VAULT_MINTS, VaultError::RegistryFull);
+
+        self.registry.owner = self.signer.key();
+        self.registry.bump = bump.registry;
+        self.registry.mints.push(self.mint.key());
+
+        let generation = self.registry.vaults_opened;
+        self.registry.vaults_opened = generation.checked_add(1).ok_or(VaultError::MathOverflow)?;
+
+        self.vault_state.set_inner(VaultState {
+            owner: self.signer.key(),
+            mint: self.mint.key(),
+            unlock_at,
+            signers: Vec::new(),
+            threshold: 0,
+            proposal_count: 0,
+            generation,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            guardian: None,
+            backup: None,
+            recovery_started_at: None,
+            vault_bump: bump.vault,
+            state_bump: bump.vault_state,
+        });
+        msg!("Vault account initialized successfully!");
+        Ok(())
+    }
+}
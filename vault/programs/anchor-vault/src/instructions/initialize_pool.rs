@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::helpers::assert_supported_mint;
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", mint.key().as_ref(), admin.key().as_ref()], // one pool per admin, so nobody can squat a mint
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"pool_vault", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_vault,
+        token::token_program = token_program,
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"shares", pool.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = pool_vault,
+        mint::token_program = token_program,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> InitializePool<'info> {
+    pub fn initialize_pool(&mut self, bumps: &InitializePoolBumps) -> Result<()> {
+        assert_supported_mint(&self.mint)?;
+
+        self.pool.set_inner(Pool {
+            admin: self.admin.key(),
+            mint: self.mint.key(),
+            share_mint: self.share_mint.key(),
+            bump: bumps.pool,
+            vault_bump: bumps.pool_vault,
+            share_mint_bump: bumps.share_mint,
+        });
+
+        msg!("Pool initialized successfully!");
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct InitializeSol<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"sol_state", signer.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"sol_vault", signer.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeSol<'info> {
+    pub fn initialize_sol(&mut self, unlock_at: i64, bump: &InitializeSolBumps) -> Result<()> {
+        self.vault_state.set_inner(VaultState {
+            owner: self.signer.key(),
+            mint: System::id(),
+            unlock_at,
+            signers: Vec::new(),
+            threshold: 0,
+            proposal_count: 0,
+            generation: 0,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            guardian: None,
+            backup: None,
+            recovery_started_at: None,
+            vault_bump: bump.vault,
+            state_bump: bump.vault_state,
+        });
+        msg!("SOL vault initialized successfully!");
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+pub mod initialize;
+pub use initialize::*;
+
+pub mod payment;
+pub use payment::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod extend_lock;
+pub use extend_lock::*;
+
+pub mod approve_delegate;
+pub use approve_delegate::*;
+
+pub mod delegated_withdraw;
+pub use delegated_withdraw::*;
+
+pub mod create_stream;
+pub use create_stream::*;
+
+pub mod claim_stream;
+pub use claim_stream::*;
+
+pub mod set_multisig;
+pub use set_multisig::*;
+
+pub mod propose_withdraw;
+pub use propose_withdraw::*;
+
+pub mod approve;
+pub use approve::*;
+
+pub mod execute;
+pub use execute::*;
+
+pub mod propose_close;
+pub use propose_close::*;
+
+pub mod execute_close;
+pub use execute_close::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod recovery;
+pub use recovery::*;
+
+pub mod execute_recovery;
+pub use execute_recovery::*;
+
+pub mod close;
+pub use close::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod initialize_pool;
+pub use initialize_pool::*;
+
+pub mod pool_payment;
+pub use pool_payment::*;
+
+pub mod donate;
+pub use donate::*;
+
+pub mod initialize_sol;
+pub use initialize_sol::*;
+
+pub mod sol_payment;
+pub use sol_payment::*;
+
+pub mod close_sol;
+pub use close_sol::*;
+
+pub mod sol_recovery;
+pub use sol_recovery::*;
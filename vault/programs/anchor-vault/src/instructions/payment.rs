@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::VaultError;
+use crate::events::DepositEvent;
+use crate::helpers::enforce_config;
+use crate::state::{UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct Payment<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program,
+    )]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.vault_bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"position", config.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // required once the mint has a config
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Payment<'info> {
+    pub fn deposit(&mut self, amount: u64, proof: Vec<[u8; 32]>, bumps: &PaymentBumps) -> Result<()> {
+        let balance_before = self.vault.amount;
+
+        let cpi_accounts = TransferChecked {
+            from: self.signer_token_account.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.signer.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        // Transfer-fee mints withhold part of the transfer, so credit what actually landed.
+        self.vault.reload()?;
+        let credited = self.vault.amount.checked_sub(balance_before).ok_or(VaultError::MathOverflow)?;
+
+        self.vault_state.record_deposit(credited)?;
+
+        enforce_config(&self.config, self.user_position.as_mut(), bumps.user_position, &self.signer.key(), &proof, credited)?;
+
+        emit!(DepositEvent {
+            owner: self.signer.key(),
+            mint: self.mint.key(),
+            amount: credited,
+            balance: self.vault.amount,
+            total_deposited: self.vault_state.total_deposited,
+        });
+
+        msg!("Deposited {} tokens to vault", credited);
+        Ok(())
+    }
+}
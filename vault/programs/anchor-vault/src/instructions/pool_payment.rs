@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::VaultError;
+use crate::events::{PoolDepositEvent, PoolWithdrawEvent};
+use crate::helpers::{enforce_config, release_config};
+use crate::state::{Pool, UserPosition};
+
+#[derive(Accounts)]
+pub struct PoolPayment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref(), pool.admin.as_ref()],
+        bump = pool.bump,
+        has_one = mint,
+        has_one = share_mint,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", pool.key().as_ref()],
+        bump = pool.vault_bump,
+        token::mint = mint,
+        token::authority = pool_vault,
+        token::token_program = token_program,
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", pool.key().as_ref()],
+        bump = pool.share_mint_bump,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = share_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: pinned to the mint's config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"position", config.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // required once the mint has a config
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> PoolPayment<'info> {
+    pub fn pool_deposit(&mut self, amount: u64, proof: Vec<[u8; 32]>, bumps: &PoolPaymentBumps) -> Result<()> {
+        let assets_before = self.pool_vault.amount;
+
+        let cpi_accounts = TransferChecked {
+            from: self.user_token_account.to_account_info(),
+            to: self.pool_vault.to_account_info(),
+            authority: self.user.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.pool_vault.reload()?;
+        let credited = self.pool_vault.amount.checked_sub(assets_before).ok_or(VaultError::MathOverflow)?;
+
+        let shares = Pool::shares_for_deposit(credited, assets_before, self.share_mint.supply)?;
+        require!(shares > 0, VaultError::ZeroShares);
+
+        let pool_key = self.pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_vault", pool_key.as_ref(), &[self.pool.vault_bump]]];
+
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info(),
+            to: self.user_share_account.to_account_info(),
+            authority: self.pool_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        mint_to(cpi_ctx, shares)?;
+
+        enforce_config(&self.config, self.user_position.as_mut(), bumps.user_position, &self.user.key(), &proof, credited)?;
+
+        emit!(PoolDepositEvent {
+            user: self.user.key(),
+            pool: pool_key,
+            amount: credited,
+            shares,
+            total_assets: self.pool_vault.amount,
+        });
+
+        msg!("Deposited {} tokens for {} shares", credited, shares);
+        Ok(())
+    }
+
+    pub fn pool_withdraw(&mut self, shares: u64) -> Result<()> {
+        let amount = Pool::assets_for_shares(shares, self.pool_vault.amount, self.share_mint.supply)?;
+        require!(amount > 0, VaultError::ZeroShares);
+        require!(amount <= self.pool_vault.amount, VaultError::InsufficientFunds);
+
+        let cpi_accounts = Burn {
+            mint: self.share_mint.to_account_info(),
+            from: self.user_share_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        burn(cpi_ctx, shares)?;
+
+        let pool_key = self.pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_vault", pool_key.as_ref(), &[self.pool.vault_bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.pool_vault.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.pool_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+
+        self.pool_vault.reload()?;
+        emit!(PoolWithdrawEvent {
+            user: self.user.key(),
+            pool: pool_key,
+            amount,
+            shares,
+            total_assets: self.pool_vault.amount,
+        });
+
+        msg!("Redeemed {} shares for {} tokens", shares, amount);
+        Ok(())
+    }
+}
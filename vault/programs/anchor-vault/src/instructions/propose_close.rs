@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::error::VaultError;
+use crate::state::{Proposal, ProposalKind, VaultState};
+
+#[derive(Accounts)]
+pub struct ProposeClose<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", vault_state.key().as_ref(), vault_state.generation.to_le_bytes().as_ref(), vault_state.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeClose<'info> {
+    pub fn propose_close(&mut self, bumps: &ProposeCloseBumps) -> Result<()> {
+        self.vault_state.assert_member(&self.proposer.key())?;
+
+        let id = self.vault_state.proposal_count;
+
+        self.proposal.set_inner(Proposal {
+            vault_state: self.vault_state.key(),
+            id,
+            proposer: self.proposer.key(),
+            kind: ProposalKind::Close,
+            destination: self.owner.key(), // vault and state rent go back to the owner
+            amount: 0,
+            approvals: vec![self.proposer.key()],
+            bump: bumps.proposal,
+        });
+
+        self.vault_state.proposal_count = id.checked_add(1).ok_or(VaultError::MathOverflow)?;
+
+        msg!("Proposal {} created to close the vault", id);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct StartRecovery<'info> {
+    pub guardian: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> StartRecovery<'info> {
+    pub fn start_recovery(&mut self) -> Result<()> {
+        let now = self.vault_state.start_recovery(&self.guardian.key())?;
+
+        msg!("Recovery started at {}", now);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> CancelRecovery<'info> {
+    pub fn cancel_recovery(&mut self) -> Result<()> {
+        self.vault_state.cancel_recovery()?;
+
+        msg!("Recovery cancelled successfully!");
+        Ok(())
+    }
+}
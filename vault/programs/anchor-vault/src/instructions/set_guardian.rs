@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub signer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> SetGuardian<'info> {
+    pub fn set_guardian(&mut self, guardian: Option<Pubkey>, backup: Option<Pubkey>) -> Result<()> {
+        self.vault_state.set_guardian(guardian, backup)?;
+
+        msg!("Vault guardian updated successfully!");
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::MAX_MULTISIG_SIGNERS;
+use crate::error::VaultError;
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct SetMultisig<'info> {
+    pub signer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> SetMultisig<'info> {
+    pub fn set_multisig(&mut self, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(!self.vault_state.is_multisig(), VaultError::MultisigAlreadySet);
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            VaultError::InvalidMultisig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            VaultError::InvalidMultisig
+        );
+
+        for (i, key) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(key), VaultError::InvalidMultisig);
+        }
+
+        self.vault_state.signers = signers;
+        self.vault_state.threshold = threshold;
+
+        // A guardian set by the single owner would otherwise bypass the new threshold.
+        self.vault_state.guardian = None;
+        self.vault_state.backup = None;
+        self.vault_state.recovery_started_at = None;
+
+        msg!("Vault now requires {} of {} signers", threshold, self.vault_state.signers.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::error::VaultError;
+use crate::events::{DepositEvent, WithdrawEvent};
+use crate::helpers::{enforce_config, release_config};
+use crate::state::{UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct SolPayment<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", signer.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: pinned to the SOL config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", System::id().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"position", config.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // required once the mint has a config
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SolPayment<'info> {
+    pub fn deposit_sol(&mut self, amount: u64, proof: Vec<[u8; 32]>, bumps: &SolPaymentBumps) -> Result<()> {
+        let rent_exempt = Rent::get()?.minimum_balance(self.vault.data_len());
+        require!(
+            self.vault.lamports().checked_add(amount).ok_or(VaultError::MathOverflow)? >= rent_exempt,
+            VaultError::BelowRentExemption
+        );
+
+        let cpi_accounts = Transfer {
+            from: self.signer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        self.vault_state.record_deposit(amount)?;
+
+        enforce_config(&self.config, self.user_position.as_mut(), bumps.user_position, &self.signer.key(), &proof, amount)?;
+
+        emit!(DepositEvent {
+            owner: self.signer.key(),
+            mint: self.vault_state.mint,
+            amount,
+            balance: self.vault.lamports(),
+            total_deposited: self.vault_state.total_deposited,
+        });
+
+        msg!("Deposited {} lamports to vault", amount);
+        Ok(())
+    }
+
+    pub fn withdraw_sol(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.assert_unlocked()?;
+
+        let rent_exempt = Rent::get()?.minimum_balance(self.vault.data_len());
+        let remaining = self.vault.lamports().checked_sub(amount).ok_or(VaultError::InsufficientFunds)?;
+        require!(remaining >= rent_exempt, VaultError::BelowRentExemption);
+
+        let signer_key = self.signer.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sol_vault", signer_key.as_ref(), &[self.vault_state.vault_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.signer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)?;
+
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+
+        emit!(WithdrawEvent {
+            owner: signer_key,
+            mint: self.vault_state.mint,
+            amount,
+            remaining,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Withdrew {} lamports from vault", amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::helpers::release_config;
+use crate::state::{UserPosition, VaultState};
+
+#[derive(Accounts)]
+pub struct SetSolGuardian<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> SetSolGuardian<'info> {
+    pub fn set_sol_guardian(&mut self, guardian: Option<Pubkey>, backup: Option<Pubkey>) -> Result<()> {
+        self.vault_state.set_guardian(guardian, backup)?;
+
+        msg!("SOL vault guardian updated successfully!");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct StartSolRecovery<'info> {
+    pub guardian: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", owner.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> StartSolRecovery<'info> {
+    pub fn start_sol_recovery(&mut self) -> Result<()> {
+        let now = self.vault_state.start_recovery(&self.guardian.key())?;
+
+        msg!("SOL recovery started at {}", now);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelSolRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> CancelSolRecovery<'info> {
+    pub fn cancel_sol_recovery(&mut self) -> Result<()> {
+        self.vault_state.cancel_recovery()?;
+
+        msg!("SOL recovery cancelled successfully!");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSolRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = Some(backup.key()) == vault_state.backup @ VaultError::InvalidGuardian,
+    )]
+    pub backup: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_state", owner.key().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", owner.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: pinned to the SOL config PDA, which may not have been created
+    #[account(
+        mut,
+        seeds = [b"config", System::id().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", config.key().as_ref(), vault_state.owner.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>, // omitted when the owner has no position
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteSolRecovery<'info> {
+    pub fn execute_sol_recovery(&mut self) -> Result<()> {
+        self.vault_state.finish_recovery()?;
+
+        // The vault holds no data, so sweeping its rent reserve too is safe.
+        let amount = self.vault.lamports();
+
+        let owner_key = self.owner.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sol_vault", owner_key.as_ref(), &[self.vault_state.vault_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.backup.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)?;
+
+        self.vault_state.record_withdrawal(amount)?;
+        release_config(&self.config, self.user_position.as_mut(), amount)?;
+
+        emit!(WithdrawEvent {
+            owner: owner_key,
+            mint: self.vault_state.mint,
+            amount,
+            remaining: 0,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        msg!("Recovered {} lamports to backup", amount);
+        Ok(())
+    }
+}
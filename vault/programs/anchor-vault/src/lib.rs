@@ -1,6 +1,16 @@
 #![allow(unexpected_cfgs)]
+pub mod constants;
+pub mod error;
+pub mod events;
+pub mod helpers;
+pub mod instructions;
+pub mod state;
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{*, TransferChecked, transfer_checked};
+
+pub use constants::*;
+pub use instructions::*;
+pub use state::*;
 
 declare_id!("FpR4hw1qbcCFCLurXr28pfzvYr3eQ4Yrfp6sZWgkRKJw");
 
@@ -8,182 +18,178 @@ declare_id!("FpR4hw1qbcCFCLurXr28pfzvYr3eQ4Yrfp6sZWgkRKJw");
 pub mod anchor_vault {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        ctx.accounts.initialize(&ctx.bumps)?;
+    pub fn initialize(ctx: Context<Initialize>, unlock_at: i64) -> Result<()> {
+        ctx.accounts.initialize(unlock_at, &ctx.bumps)?;
         Ok(())
     }
 
-    pub fn deposit(ctx: Context<Payment>, amount: u64) -> Result<()> {
-        ctx.accounts.deposit(amount)?;
+    pub fn deposit(ctx: Context<Payment>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.deposit(amount, proof, &ctx.bumps)?;
         Ok(())
     }
 
-    pub fn close(ctx: Context<Close>) -> Result<()> {
-        let vault_state = &ctx.accounts.vault_state;
-        let signer = &ctx.accounts.signer;
-        let vault = &ctx.accounts.vault;
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)?;
+        Ok(())
+    }
 
-        let signer_key = signer.key();
-        let vault_seeds: &[&[&[u8]]]  = &[
-            &[
-                b"vault",
-                signer_key.as_ref(),
-                &[vault_state.vault_bump],
-            ],
-        ];
+    pub fn extend_lock(ctx: Context<ExtendLock>, unlock_at: i64) -> Result<()> {
+        ctx.accounts.extend_lock(unlock_at)?;
+        Ok(())
+    }
 
-        let cpi_accounts = CloseAccount {
-            account: vault.to_account_info(),
-            destination: signer.to_account_info(),
-            authority: vault.to_account_info(),
-        };
+    pub fn approve_delegate(ctx: Context<ApproveDelegate>, amount: u64, expires_at: i64) -> Result<()> {
+        ctx.accounts.approve_delegate(amount, expires_at, &ctx.bumps)?;
+        Ok(())
+    }
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            vault_seeds,
-        );
+    pub fn revoke_delegate(_ctx: Context<RevokeDelegate>) -> Result<()> {
+        msg!("Allowance revoked successfully!");
+        Ok(())
+    }
 
-        close_account(cpi_context)?;
+    pub fn delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.delegated_withdraw(amount)?;
+        Ok(())
+    }
 
-        msg!("Vault account closed successfully!");
+    pub fn create_stream(ctx: Context<CreateStream>, rate_per_second: u64, start_time: i64, end_time: i64) -> Result<()> {
+        ctx.accounts.create_stream(rate_per_second, start_time, end_time, &ctx.bumps)?;
+        Ok(())
+    }
 
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        ctx.accounts.cancel_stream()?;
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info>{
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    pub mint: Account<'info, Mint>,
-
-    #[account(
-        init,
-        payer = signer,
-        space = 8 + VaultState::INIT_SPACE,
-        seeds = [b"state", signer.key().as_ref()],
-        bump
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    #[account(
-        init,
-        payer = signer,
-        seeds = [b"vault", signer.key().as_ref()],
-        bump,
-        token::mint = mint,
-        token::authority = vault,
-    )]
-    pub vault: Account<'info, TokenAccount>,
-
-    pub system_program: Program<'info, System>,
-
-    pub token_program: Program<'info, Token>,
-}
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        ctx.accounts.claim_stream()?;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Payment<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub fn set_multisig(ctx: Context<SetMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        ctx.accounts.set_multisig(signers, threshold)?;
+        Ok(())
+    }
 
-    pub mint: Account<'info, Mint>,
+    pub fn propose_withdraw(ctx: Context<ProposeWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.propose_withdraw(amount, &ctx.bumps)?;
+        Ok(())
+    }
 
-    #[account(
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump,
-    )]
-    pub vault_state: Account<'info, VaultState>,
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        ctx.accounts.approve()?;
+        Ok(())
+    }
 
-    #[account(
-        mut,
-        seeds = [b"vault", signer.key().as_ref()],
-        bump = vault_state.vault_bump,
-        token::mint = mint,
-        token::authority = vault,
-    )]
-    pub vault: Account<'info, TokenAccount>,
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {
+        ctx.accounts.execute()?;
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
+    pub fn propose_close(ctx: Context<ProposeClose>) -> Result<()> {
+        ctx.accounts.propose_close(&ctx.bumps)?;
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token>,
-}
+    pub fn execute_close(ctx: Context<ExecuteClose>) -> Result<()> {
+        ctx.accounts.execute_close()?;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Close<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump,
-        close = signer,
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    #[account(
-        mut,
-        seeds = [b"vault", signer.key().as_ref()],
-        bump = vault_state.vault_bump,
-        close = signer,
-    )]
-    pub vault: Account<'info, TokenAccount>,
-
-    pub system_program: Program<'info, System>,
-
-    pub token_program: Program<'info, Token>,
-}
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>, backup: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.set_guardian(guardian, backup)?;
+        Ok(())
+    }
 
-impl<'info> Initialize<'info> {
-    pub fn initialize(&mut self, bump: &InitializeBumps) -> Result<()> {
-        self.vault_state.vault_bump = bump.vault;
-        self.vault_state.state_bump = bump.vault_state;
-        msg!("Vault account initialized successfully!");
+    pub fn start_recovery(ctx: Context<StartRecovery>) -> Result<()> {
+        ctx.accounts.start_recovery()?;
+        Ok(())
+    }
+
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        ctx.accounts.cancel_recovery()?;
         Ok(())
     }
-}
 
-impl<'info> Payment<'info> {
-    pub fn deposit(&mut self, amount: u64) -> Result<()> {
-        let cpi_accounts = TransferChecked {
-            from: self.signer.to_account_info(),
-            to: self.vault.to_account_info(),
-            authority: self.signer.to_account_info(),
-            mint: self.mint.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        ctx.accounts.execute_recovery()?;
+        Ok(())
+    }
 
-        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        ctx.accounts.close()?;
+        Ok(())
+    }
 
-        msg!("Deposited {} tokens to vault", amount);
+    pub fn initialize_config(ctx: Context<InitializeConfig>, per_user_cap: Option<u64>, global_cap: Option<u64>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        ctx.accounts.initialize_config(per_user_cap, global_cap, allowlist_root, &ctx.bumps)?;
         Ok(())
     }
 
-    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
-        let signer = self.signer.to_account_info();
-        let signer_seeds: &[&[&[u8]]] = &[&[b"vault", signer.key.as_ref(), &[self.vault_state.vault_bump]]];
+    pub fn update_config(ctx: Context<UpdateConfig>, per_user_cap: Option<u64>, global_cap: Option<u64>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        ctx.accounts.update_config(per_user_cap, global_cap, allowlist_root)?;
+        Ok(())
+    }
 
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.signer.to_account_info(),
-            authority: self.vault.to_account_info(),
-            mint : self.mint.to_account_info(),
-        };
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        ctx.accounts.initialize_pool(&ctx.bumps)?;
+        Ok(())
+    }
 
-        let cpi_context = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    pub fn pool_deposit(ctx: Context<PoolPayment>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.pool_deposit(amount, proof, &ctx.bumps)?;
+        Ok(())
+    }
 
-        transfer_checked(cpi_context, amount, self.mint.decimals)?;
-        msg!("Withdrew {} tokens from vault", amount);
+    pub fn pool_withdraw(ctx: Context<PoolPayment>, shares: u64) -> Result<()> {
+        ctx.accounts.pool_withdraw(shares)?;
+        Ok(())
+    }
 
+    pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
+        ctx.accounts.donate(amount)?;
+        Ok(())
+    }
+
+    pub fn initialize_sol(ctx: Context<InitializeSol>, unlock_at: i64) -> Result<()> {
+        ctx.accounts.initialize_sol(unlock_at, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn deposit_sol(ctx: Context<SolPayment>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.deposit_sol(amount, proof, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn withdraw_sol(ctx: Context<SolPayment>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_sol(amount)?;
         Ok(())
     }
-}
 
-#[account]
-#[derive(InitSpace)]
-pub struct VaultState {
-    pub vault_bump: u8,
-    pub state_bump: u8,
+    pub fn close_sol(ctx: Context<CloseSol>) -> Result<()> {
+        ctx.accounts.close_sol()?;
+        Ok(())
+    }
+
+    pub fn set_sol_guardian(ctx: Context<SetSolGuardian>, guardian: Option<Pubkey>, backup: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.set_sol_guardian(guardian, backup)?;
+        Ok(())
+    }
+
+    pub fn start_sol_recovery(ctx: Context<StartSolRecovery>) -> Result<()> {
+        ctx.accounts.start_sol_recovery()?;
+        Ok(())
+    }
+
+    pub fn cancel_sol_recovery(ctx: Context<CancelSolRecovery>) -> Result<()> {
+        ctx.accounts.cancel_sol_recovery()?;
+        Ok(())
+    }
+
+    pub fn execute_sol_recovery(ctx: Context<ExecuteSolRecovery>) -> Result<()> {
+        ctx.accounts.execute_sol_recovery()?;
+        Ok(())
+    }
 }
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub vault_state: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64, // what the delegate can still pull
+    pub expires_at: i64,
+    pub bump: u8,
+}
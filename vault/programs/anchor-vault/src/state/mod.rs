@@ -0,0 +1,23 @@
+pub mod vault_state;
+pub use vault_state::*;
+
+pub mod vault_registry;
+pub use vault_registry::*;
+
+pub mod allowance;
+pub use allowance::*;
+
+pub mod proposal;
+pub use proposal::*;
+
+pub mod stream;
+pub use stream::*;
+
+pub mod pool;
+pub use pool::*;
+
+pub mod vault_config;
+pub use vault_config::*;
+
+pub mod user_position;
+pub use user_position::*;
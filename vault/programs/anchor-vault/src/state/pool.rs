@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub share_mint: Pubkey,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub share_mint_bump: u8,
+}
+
+impl Pool {
+    // One virtual share and one virtual token keep the first depositor from
+    // inflating the share price with a donation to an empty pool.
+    pub fn shares_for_deposit(amount: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+        Self::mul_div(amount, total_shares as u128 + 1, total_assets as u128 + 1)
+    }
+
+    pub fn assets_for_shares(shares: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+        Self::mul_div(shares, total_assets as u128 + 1, total_shares as u128 + 1)
+    }
+
+    fn mul_div(value: u64, numerator: u128, denominator: u128) -> Result<u64> {
+        let result = (value as u128)
+            .checked_mul(numerator)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(denominator)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(u64::try_from(result).map_err(|_| VaultError::MathOverflow)?)
+    }
+}
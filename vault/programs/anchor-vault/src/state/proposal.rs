@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_MULTISIG_SIGNERS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalKind {
+    Withdraw,
+    Close,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub vault_state: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub kind: ProposalKind,
+    pub destination: Pubkey, // token account the funds go to on execute
+    pub amount: u64,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+}
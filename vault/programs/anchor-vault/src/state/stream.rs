@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub vault_state: Pubkey,
+    pub recipient: Pubkey,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub last_claimed_at: i64, // everything up to here has been paid out
+    pub bump: u8,
+}
+
+impl Stream {
+    pub fn vested_since_last_claim(&self, now: i64) -> Result<(u64, i64)> {
+        let until = now.min(self.end_time);
+        let from = self.last_claimed_at.max(self.start_time);
+
+        if until <= from {
+            return Ok((0, self.last_claimed_at));
+        }
+
+        let elapsed = (until - from) as u64;
+        let vested = elapsed.checked_mul(self.rate_per_second).ok_or(VaultError::InvalidStream)?;
+        Ok((vested, until))
+    }
+}
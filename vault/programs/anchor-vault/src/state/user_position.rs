@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPosition {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub net_deposited: u64, // across the user's vault and every pool of the config's mint
+    pub bump: u8,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultConfig {
+    pub admin: Pubkey,
+    pub mint: Pubkey, // system program id for lamport vaults
+    pub per_user_cap: Option<u64>, // net deposits a single user may hold, tracked in their UserPosition
+    pub global_cap: Option<u64>, // net balance allowed across all vaults and pools of the mint
+    pub allowlist_root: Option<[u8; 32]>, // merkle root of keccak(depositor) leaves
+    pub net_deposited: u64, // deposits minus withdrawals since the config was created
+    pub bump: u8,
+}
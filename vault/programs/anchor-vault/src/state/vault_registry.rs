@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_VAULT_MINTS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultRegistry {
+    pub owner: Pubkey,
+    #[max_len(MAX_VAULT_MINTS)]
+    pub mints: Vec<Pubkey>, // mints the owner currently has an open vault for
+    pub vaults_opened: u64, // never decreases, so a reopened vault gets a new generation
+    pub bump: u8,
+}
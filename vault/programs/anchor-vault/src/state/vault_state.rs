@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_MULTISIG_SIGNERS, RECOVERY_CHALLENGE_WINDOW};
+use crate::error::VaultError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState {
+    pub owner: Pubkey,
+    pub mint: Pubkey, // system program id for lamport vaults
+    pub unlock_at: i64, // unix timestamp before which nothing can leave the vault
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>, // empty while the vault is controlled by the owner alone
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub generation: u64, // seeds proposals, allowances and streams so a vault reopened at the same address starts clean
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub guardian: Option<Pubkey>,
+    pub backup: Option<Pubkey>, // wallet the guardian can move funds to
+    pub recovery_started_at: Option<i64>,
+    pub vault_bump: u8,
+    pub state_bump: u8,
+}
+
+impl VaultState {
+    pub fn assert_unlocked(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.unlock_at, VaultError::VaultLocked);
+        Ok(())
+    }
+
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self.total_deposited.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.total_withdrawn = self.total_withdrawn.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn is_multisig(&self) -> bool {
+        !self.signers.is_empty()
+    }
+
+    pub fn assert_single_owner(&self) -> Result<()> {
+        require!(!self.is_multisig(), VaultError::MultisigRequired);
+        Ok(())
+    }
+
+    pub fn assert_member(&self, key: &Pubkey) -> Result<()> {
+        require!(self.is_multisig(), VaultError::NotMultisig);
+        require!(self.signers.contains(key), VaultError::NotMultisigMember);
+        Ok(())
+    }
+
+    pub fn set_guardian(&mut self, guardian: Option<Pubkey>, backup: Option<Pubkey>) -> Result<()> {
+        self.assert_single_owner()?;
+        require!(guardian.is_some() == backup.is_some(), VaultError::InvalidGuardian);
+
+        self.guardian = guardian;
+        self.backup = backup;
+        self.recovery_started_at = None;
+        Ok(())
+    }
+
+    pub fn start_recovery(&mut self, guardian: &Pubkey) -> Result<i64> {
+        self.assert_single_owner()?;
+        require!(self.guardian == Some(*guardian), VaultError::NotGuardian);
+        require!(self.recovery_started_at.is_none(), VaultError::RecoveryPending);
+
+        let now = Clock::get()?.unix_timestamp;
+        self.recovery_started_at = Some(now);
+        Ok(now)
+    }
+
+    pub fn cancel_recovery(&mut self) -> Result<()> {
+        require!(self.recovery_started_at.is_some(), VaultError::NoRecoveryPending);
+
+        self.recovery_started_at = None;
+        Ok(())
+    }
+
+    // The owner picks the guardian, so recovery still waits out the timelock rather than bypassing it.
+    pub fn finish_recovery(&mut self) -> Result<()> {
+        self.assert_single_owner()?;
+
+        let started_at = self.recovery_started_at.ok_or(VaultError::NoRecoveryPending)?;
+        let ready_at = started_at.checked_add(RECOVERY_CHALLENGE_WINDOW).ok_or(VaultError::MathOverflow)?;
+        require!(Clock::get()?.unix_timestamp >= ready_at, VaultError::ChallengeWindowActive);
+        self.assert_unlocked()?;
+
+        self.recovery_started_at = None;
+        Ok(())
+    }
+}
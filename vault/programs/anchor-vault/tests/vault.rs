@@ -0,0 +1,848 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, system_instruction},
+    system_program, AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_vault::state::VaultRegistry;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account};
+
+const DECIMALS: u8 = 6;
+const ONE: u64 = 1_000_000;
+
+struct Test {
+    ctx: ProgramTestContext,
+    owner: Keypair,
+    mint: Pubkey,
+    owner_ata: Pubkey,
+}
+
+// Anchor's entrypoint ties the account slice to the `AccountInfo` lifetime, which the
+// native processor signature does not; the leaked copy shares the same account data.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    anchor_vault::entry(program_id, accounts, data)
+}
+
+async fn send(ctx: &mut ProgramTestContext, ixs: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Result<(), BanksClientError> {
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), signers, ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+async fn setup() -> Test {
+    let owner = Keypair::new();
+    let mut program_test = ProgramTest::new("anchor_vault", anchor_vault::ID, processor!(process_instruction));
+    program_test.add_account(owner.pubkey(), Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program::ID));
+    let mut ctx = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Mint::LEN);
+    send(
+        &mut ctx,
+        &[
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &owner.pubkey(), None, DECIMALS).unwrap(),
+        ],
+        &owner,
+        &[&owner, &mint],
+    )
+    .await
+    .unwrap();
+
+    let mint = mint.pubkey();
+    let owner_ata = create_funded_ata(&mut ctx, &owner, &owner.pubkey(), &mint, 100 * ONE).await;
+
+    Test { ctx, owner, mint, owner_ata }
+}
+
+async fn create_funded_ata(ctx: &mut ProgramTestContext, payer: &Keypair, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+    let ata = get_associated_token_address(owner, mint);
+    send(
+        ctx,
+        &[
+            create_associated_token_account(&payer.pubkey(), owner, mint, &spl_token::ID),
+            spl_token::instruction::mint_to(&spl_token::ID, mint, &ata, &payer.pubkey(), &[], amount).unwrap(),
+        ],
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+    ata
+}
+
+async fn token_balance(ctx: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = ctx.banks_client.get_account(*account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn is_closed(ctx: &mut ProgramTestContext, account: &Pubkey) -> bool {
+    ctx.banks_client.get_account(*account).await.unwrap().is_none_or(|a| a.lamports == 0)
+}
+
+fn pdas(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+    let (registry, _) = Pubkey::find_program_address(&[b"registry", owner.as_ref()], &anchor_vault::ID);
+    let (vault_state, _) = Pubkey::find_program_address(&[b"state", owner.as_ref(), mint.as_ref()], &anchor_vault::ID);
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", owner.as_ref(), mint.as_ref()], &anchor_vault::ID);
+    (registry, vault_state, vault)
+}
+
+fn initialize_ix(owner: &Pubkey, mint: &Pubkey, unlock_at: i64) -> Instruction {
+    let (registry, vault_state, vault) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Initialize {
+            signer: *owner,
+            mint: *mint,
+            registry,
+            vault_state,
+            vault,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::Initialize { unlock_at }.data(),
+    }
+}
+
+fn config_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"config", mint.as_ref()], &anchor_vault::ID).0
+}
+
+fn position_pda(config: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"position", config.as_ref(), user.as_ref()], &anchor_vault::ID).0
+}
+
+fn deposit_ix(owner: &Pubkey, mint: &Pubkey, signer_token_account: &Pubkey, amount: u64) -> Instruction {
+    let (_, vault_state, vault) = pdas(owner, mint);
+    let config = config_pda(mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Payment {
+            signer: *owner,
+            mint: *mint,
+            signer_token_account: *signer_token_account,
+            vault_state,
+            vault,
+            config,
+            user_position: Some(position_pda(&config, owner)),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::Deposit { amount, proof: vec![] }.data(),
+    }
+}
+
+fn withdraw_ix(signer: &Pubkey, vault_owner: &Pubkey, mint: &Pubkey, signer_token_account: &Pubkey, amount: u64) -> Instruction {
+    let (_, vault_state, vault) = pdas(vault_owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Withdraw {
+            signer: *signer,
+            mint: *mint,
+            signer_token_account: *signer_token_account,
+            vault_state,
+            vault,
+            config: config_pda(mint),
+            user_position: Some(position_pda(&config_pda(mint), vault_owner)),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::Withdraw { amount }.data(),
+    }
+}
+
+fn close_ix(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (registry, vault_state, vault) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Close {
+            signer: *owner,
+            mint: *mint,
+            registry,
+            vault_state,
+            vault,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::Close {}.data(),
+    }
+}
+
+async fn initialize_and_deposit(t: &mut Test, unlock_at: i64, amount: u64) {
+    let owner = t.owner.pubkey();
+    send(
+        &mut t.ctx,
+        &[
+            initialize_ix(&owner, &t.mint, unlock_at),
+            deposit_ix(&owner, &t.mint, &t.owner_ata, amount),
+        ],
+        &t.owner,
+        &[&t.owner],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn deposit_withdraw_and_close() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 40 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (registry, vault_state, vault) = pdas(&owner, &t.mint);
+
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 40 * ONE);
+    assert_eq!(token_balance(&mut t.ctx, &t.owner_ata).await, 60 * ONE);
+
+    send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, 15 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 25 * ONE);
+    assert_eq!(token_balance(&mut t.ctx, &t.owner_ata).await, 75 * ONE);
+
+    send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, 25 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[close_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await.unwrap();
+
+    assert_eq!(token_balance(&mut t.ctx, &t.owner_ata).await, 100 * ONE);
+    assert!(is_closed(&mut t.ctx, &vault).await);
+    assert!(is_closed(&mut t.ctx, &vault_state).await);
+
+    let registry = t.ctx.banks_client.get_account(registry).await.unwrap().unwrap();
+    let registry = VaultRegistry::try_deserialize(&mut registry.data.as_slice()).unwrap();
+    assert!(registry.mints.is_empty());
+}
+
+#[tokio::test]
+async fn withdraw_more_than_vault_balance_fails() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (_, _, vault) = pdas(&owner, &t.mint);
+
+    let result = send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, 11 * ONE)], &t.owner, &[&t.owner]).await;
+
+    assert!(result.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 10 * ONE);
+}
+
+#[tokio::test]
+async fn withdraw_with_wrong_signer_fails() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (_, _, vault) = pdas(&owner, &t.mint);
+
+    let attacker = Keypair::new();
+    let owner_keypair = t.owner.insecure_clone();
+    send(
+        &mut t.ctx,
+        &[system_instruction::transfer(&owner, &attacker.pubkey(), LAMPORTS_PER_SOL)],
+        &owner_keypair,
+        &[&owner_keypair],
+    )
+    .await
+    .unwrap();
+    let attacker_ata = create_funded_ata(&mut t.ctx, &owner_keypair, &attacker.pubkey(), &t.mint, 0).await;
+
+    let result = send(
+        &mut t.ctx,
+        &[withdraw_ix(&attacker.pubkey(), &owner, &t.mint, &attacker_ata, 10 * ONE)],
+        &attacker,
+        &[&attacker],
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 10 * ONE);
+    assert_eq!(token_balance(&mut t.ctx, &attacker_ata).await, 0);
+}
+
+#[tokio::test]
+async fn close_with_remaining_balance_fails() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, vault) = pdas(&owner, &t.mint);
+
+    let result = send(&mut t.ctx, &[close_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await;
+
+    assert!(result.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 10 * ONE);
+    assert!(!is_closed(&mut t.ctx, &vault_state).await);
+}
+
+#[tokio::test]
+async fn locked_vault_refuses_withdraw_until_unlock() {
+    let mut t = setup().await;
+    let now = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    initialize_and_deposit(&mut t, now + 1_000, 10 * ONE).await;
+    let owner = t.owner.pubkey();
+
+    let result = send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, ONE)], &t.owner, &[&t.owner]).await;
+    assert!(result.is_err());
+
+    let mut clock = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = now + 1_000;
+    t.ctx.set_sysvar::<Clock>(&clock);
+
+    send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    assert_eq!(token_balance(&mut t.ctx, &t.owner_ata).await, 91 * ONE);
+}
+
+fn set_multisig_ix(owner: &Pubkey, mint: &Pubkey, signers: Vec<Pubkey>, threshold: u8) -> Instruction {
+    let (_, vault_state, _) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::SetMultisig { signer: *owner, mint: *mint, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::SetMultisig { signers, threshold }.data(),
+    }
+}
+
+fn proposal_pda(vault_state: &Pubkey, generation: u64, id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"proposal", vault_state.as_ref(), generation.to_le_bytes().as_ref(), id.to_le_bytes().as_ref()],
+        &anchor_vault::ID,
+    )
+    .0
+}
+
+fn propose_close_ix(owner: &Pubkey, mint: &Pubkey, proposal: &Pubkey) -> Instruction {
+    let (_, vault_state, _) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ProposeClose {
+            proposer: *owner,
+            owner: *owner,
+            mint: *mint,
+            vault_state,
+            proposal: *proposal,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::ProposeClose {}.data(),
+    }
+}
+
+fn execute_close_ix(owner: &Pubkey, mint: &Pubkey, proposal: &Pubkey) -> Instruction {
+    let (registry, vault_state, vault) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ExecuteClose {
+            signer: *owner,
+            proposer: *owner,
+            owner: *owner,
+            mint: *mint,
+            registry,
+            vault_state,
+            proposal: *proposal,
+            vault,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::ExecuteClose {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn multisig_vault_closes_only_through_a_proposal() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 0).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, vault) = pdas(&owner, &t.mint);
+    let cosigner = Keypair::new();
+
+    send(&mut t.ctx, &[set_multisig_ix(&owner, &t.mint, vec![owner, cosigner.pubkey()], 2)], &t.owner, &[&t.owner]).await.unwrap();
+
+    let extend_lock = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ExtendLock { signer: owner, mint: t.mint, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::ExtendLock { unlock_at: i64::MAX }.data(),
+    };
+    assert!(send(&mut t.ctx, &[extend_lock], &t.owner, &[&t.owner]).await.is_err());
+    assert!(send(&mut t.ctx, &[close_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await.is_err());
+
+    let proposal = proposal_pda(&vault_state, 0, 0);
+    let propose = propose_close_ix(&owner, &t.mint, &proposal);
+    let execute = execute_close_ix(&owner, &t.mint, &proposal);
+    send(&mut t.ctx, &[propose], &t.owner, &[&t.owner]).await.unwrap();
+    assert!(send(&mut t.ctx, std::slice::from_ref(&execute), &t.owner, &[&t.owner]).await.is_err());
+
+    let approve = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Approve { signer: cosigner.pubkey(), vault_state, proposal }.to_account_metas(None),
+        data: anchor_vault::instruction::Approve {}.data(),
+    };
+    let owner_keypair = t.owner.insecure_clone();
+    send(&mut t.ctx, &[approve, execute], &owner_keypair, &[&owner_keypair, &cosigner]).await.unwrap();
+
+    assert!(is_closed(&mut t.ctx, &vault).await);
+    assert!(is_closed(&mut t.ctx, &vault_state).await);
+    assert!(is_closed(&mut t.ctx, &proposal).await);
+}
+
+fn propose_withdraw_ix(owner: &Pubkey, mint: &Pubkey, proposal: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    let (_, vault_state, _) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ProposeWithdraw {
+            proposer: *owner,
+            owner: *owner,
+            mint: *mint,
+            destination: *destination,
+            vault_state,
+            proposal: *proposal,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::ProposeWithdraw { amount }.data(),
+    }
+}
+
+fn execute_ix(owner: &Pubkey, mint: &Pubkey, proposal: &Pubkey, destination: &Pubkey) -> Instruction {
+    let (_, vault_state, vault) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::Execute {
+            signer: *owner,
+            proposer: *owner,
+            owner: *owner,
+            mint: *mint,
+            destination: *destination,
+            vault_state,
+            proposal: *proposal,
+            vault,
+            config: config_pda(mint),
+            user_position: None,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::Execute {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn reopened_vault_ignores_proposals_from_before_close() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 0).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, vault) = pdas(&owner, &t.mint);
+    let cosigner = Keypair::new().pubkey();
+
+    // A withdraw proposal that already meets the threshold is left open when the vault closes.
+    send(&mut t.ctx, &[set_multisig_ix(&owner, &t.mint, vec![owner, cosigner], 1)], &t.owner, &[&t.owner]).await.unwrap();
+    let stale = proposal_pda(&vault_state, 0, 0);
+    let closing = proposal_pda(&vault_state, 0, 1);
+    send(
+        &mut t.ctx,
+        &[
+            propose_withdraw_ix(&owner, &t.mint, &stale, &t.owner_ata, 10 * ONE),
+            propose_close_ix(&owner, &t.mint, &closing),
+            execute_close_ix(&owner, &t.mint, &closing),
+        ],
+        &t.owner,
+        &[&t.owner],
+    )
+    .await
+    .unwrap();
+    assert!(is_closed(&mut t.ctx, &vault_state).await);
+
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+    send(&mut t.ctx, &[set_multisig_ix(&owner, &t.mint, vec![owner, cosigner], 1)], &t.owner, &[&t.owner]).await.unwrap();
+    assert!(send(&mut t.ctx, &[execute_ix(&owner, &t.mint, &stale, &t.owner_ata)], &t.owner, &[&t.owner]).await.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 10 * ONE);
+
+    let fresh = proposal_pda(&vault_state, 1, 0);
+    send(&mut t.ctx, &[propose_withdraw_ix(&owner, &t.mint, &fresh, &t.owner_ata, 10 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[execute_ix(&owner, &t.mint, &fresh, &t.owner_ata)], &t.owner, &[&t.owner]).await.unwrap();
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 0);
+}
+
+fn stream_pda(vault_state: &Pubkey, generation: u64, recipient: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"stream", vault_state.as_ref(), generation.to_le_bytes().as_ref(), recipient.as_ref()],
+        &anchor_vault::ID,
+    )
+    .0
+}
+
+fn create_stream_ix(owner: &Pubkey, recipient: &Pubkey, mint: &Pubkey, rate_per_second: u64, start_time: i64, end_time: i64) -> Instruction {
+    let (_, vault_state, _) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::CreateStream {
+            signer: *owner,
+            recipient: *recipient,
+            mint: *mint,
+            vault_state,
+            stream: stream_pda(&vault_state, 0, recipient),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::CreateStream { rate_per_second, start_time, end_time }.data(),
+    }
+}
+
+fn cancel_stream_ix(owner: &Pubkey, mint: &Pubkey, recipient: &Pubkey, recipient_token_account: &Pubkey) -> Instruction {
+    let (_, vault_state, vault) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::CancelStream {
+            signer: *owner,
+            recipient: *recipient,
+            mint: *mint,
+            recipient_token_account: *recipient_token_account,
+            vault_state,
+            stream: stream_pda(&vault_state, 0, recipient),
+            vault,
+            config: config_pda(mint),
+            user_position: None,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::CancelStream {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn cancel_stream_pays_out_vested_tokens() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 50 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, vault) = pdas(&owner, &t.mint);
+    let now = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    let recipient = Keypair::new();
+    let owner_keypair = t.owner.insecure_clone();
+    let recipient_ata = create_funded_ata(&mut t.ctx, &owner_keypair, &recipient.pubkey(), &t.mint, 0).await;
+    let greedy = Keypair::new();
+    let greedy_ata = create_funded_ata(&mut t.ctx, &owner_keypair, &greedy.pubkey(), &t.mint, 0).await;
+
+    let overflowing = create_stream_ix(&owner, &recipient.pubkey(), &t.mint, u64::MAX, now, now + 2);
+    assert!(send(&mut t.ctx, &[overflowing], &t.owner, &[&t.owner]).await.is_err());
+
+    send(&mut t.ctx, &[create_stream_ix(&owner, &recipient.pubkey(), &t.mint, ONE, now, now + 100)], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[create_stream_ix(&owner, &greedy.pubkey(), &t.mint, 10 * ONE, now, now + 100)], &t.owner, &[&t.owner]).await.unwrap();
+
+    let mut clock = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = now + 10;
+    t.ctx.set_sysvar::<Clock>(&clock);
+
+    // 100 tokens have vested on the greedy stream but the vault only holds 50.
+    let greedy_stream = stream_pda(&vault_state, 0, &greedy.pubkey());
+    assert!(send(&mut t.ctx, &[cancel_stream_ix(&owner, &t.mint, &greedy.pubkey(), &greedy_ata)], &t.owner, &[&t.owner]).await.is_err());
+    assert!(!is_closed(&mut t.ctx, &greedy_stream).await);
+
+    let stream = stream_pda(&vault_state, 0, &recipient.pubkey());
+    let cancel = cancel_stream_ix(&owner, &t.mint, &recipient.pubkey(), &recipient_ata);
+    send(&mut t.ctx, &[cancel], &t.owner, &[&t.owner]).await.unwrap();
+
+    assert_eq!(token_balance(&mut t.ctx, &recipient_ata).await, 10 * ONE);
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 40 * ONE);
+    assert!(is_closed(&mut t.ctx, &stream).await);
+}
+
+fn set_guardian_ix(owner: &Pubkey, mint: &Pubkey, guardian: &Pubkey, backup: &Pubkey) -> Instruction {
+    let (_, vault_state, _) = pdas(owner, mint);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::SetGuardian { signer: *owner, mint: *mint, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::SetGuardian { guardian: Some(*guardian), backup: Some(*backup) }.data(),
+    }
+}
+
+#[tokio::test]
+async fn set_multisig_revokes_the_guardian() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, _) = pdas(&owner, &t.mint);
+    let guardian = Keypair::new();
+
+    send(&mut t.ctx, &[set_guardian_ix(&owner, &t.mint, &guardian.pubkey(), &guardian.pubkey())], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[set_multisig_ix(&owner, &t.mint, vec![owner, Keypair::new().pubkey()], 2)], &t.owner, &[&t.owner]).await.unwrap();
+
+    let start = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::StartRecovery { guardian: guardian.pubkey(), owner, mint: t.mint, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::StartRecovery {}.data(),
+    };
+    let owner_keypair = t.owner.insecure_clone();
+    assert!(send(&mut t.ctx, &[start], &owner_keypair, &[&owner_keypair, &guardian]).await.is_err());
+}
+
+#[tokio::test]
+async fn guardian_recovery_waits_for_the_lock() {
+    let mut t = setup().await;
+    let owner = t.owner.pubkey();
+    let now = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let (vault_state, _) = Pubkey::find_program_address(&[b"sol_state", owner.as_ref()], &anchor_vault::ID);
+    let (vault, _) = Pubkey::find_program_address(&[b"sol_vault", owner.as_ref()], &anchor_vault::ID);
+    let unlock_at = now + 365 * 24 * 60 * 60;
+    let guardian = Keypair::new();
+    let backup = Keypair::new().pubkey();
+
+    let initialize = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::InitializeSol { signer: owner, vault_state, vault, system_program: system_program::ID }.to_account_metas(None),
+        data: anchor_vault::instruction::InitializeSol { unlock_at }.data(),
+    };
+    let deposit = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::SolPayment {
+            signer: owner,
+            vault_state,
+            vault,
+            config: config_pda(&system_program::ID),
+            user_position: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::DepositSol { amount: LAMPORTS_PER_SOL, proof: vec![] }.data(),
+    };
+    let set_guardian = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::SetSolGuardian { signer: owner, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::SetSolGuardian { guardian: Some(guardian.pubkey()), backup: Some(backup) }.data(),
+    };
+    send(&mut t.ctx, &[initialize, deposit, set_guardian], &t.owner, &[&t.owner]).await.unwrap();
+
+    let start = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::StartSolRecovery { guardian: guardian.pubkey(), owner, vault_state }.to_account_metas(None),
+        data: anchor_vault::instruction::StartSolRecovery {}.data(),
+    };
+    let execute = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ExecuteSolRecovery {
+            signer: guardian.pubkey(),
+            owner,
+            backup,
+            vault_state,
+            vault,
+            config: config_pda(&system_program::ID),
+            user_position: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::ExecuteSolRecovery {}.data(),
+    };
+    let owner_keypair = t.owner.insecure_clone();
+    send(&mut t.ctx, &[start], &owner_keypair, &[&owner_keypair, &guardian]).await.unwrap();
+    assert!(send(&mut t.ctx, std::slice::from_ref(&execute), &owner_keypair, &[&owner_keypair, &guardian]).await.is_err());
+
+    let mut clock = t.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = now + anchor_vault::RECOVERY_CHALLENGE_WINDOW;
+    t.ctx.set_sysvar::<Clock>(&clock);
+    assert!(send(&mut t.ctx, std::slice::from_ref(&execute), &owner_keypair, &[&owner_keypair, &guardian]).await.is_err());
+
+    clock.unix_timestamp = unlock_at;
+    t.ctx.set_sysvar::<Clock>(&clock);
+    send(&mut t.ctx, &[execute], &owner_keypair, &[&owner_keypair, &guardian]).await.unwrap();
+
+    let backup_balance = t.ctx.banks_client.get_balance(backup).await.unwrap();
+    assert!(backup_balance >= LAMPORTS_PER_SOL);
+    assert!(is_closed(&mut t.ctx, &vault).await);
+}
+
+fn pool_pdas(mint: &Pubkey, admin: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+    let (pool, _) = Pubkey::find_program_address(&[b"pool", mint.as_ref(), admin.as_ref()], &anchor_vault::ID);
+    let (pool_vault, _) = Pubkey::find_program_address(&[b"pool_vault", pool.as_ref()], &anchor_vault::ID);
+    let (share_mint, _) = Pubkey::find_program_address(&[b"shares", pool.as_ref()], &anchor_vault::ID);
+    (pool, pool_vault, share_mint)
+}
+
+fn initialize_pool_ix(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (pool, pool_vault, share_mint) = pool_pdas(mint, admin);
+    Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::InitializePool {
+            admin: *admin,
+            mint: *mint,
+            pool,
+            pool_vault,
+            share_mint,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::InitializePool {}.data(),
+    }
+}
+
+fn pool_payment_accounts(user: &Pubkey, admin: &Pubkey, mint: &Pubkey, user_token_account: &Pubkey) -> Vec<anchor_lang::solana_program::instruction::AccountMeta> {
+    let (pool, pool_vault, share_mint) = pool_pdas(mint, admin);
+    anchor_vault::accounts::PoolPayment {
+        user: *user,
+        mint: *mint,
+        pool,
+        pool_vault,
+        share_mint,
+        user_token_account: *user_token_account,
+        user_share_account: get_associated_token_address(user, &share_mint),
+        config: config_pda(mint),
+        user_position: Some(position_pda(&config_pda(mint), user)),
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+    }
+    .to_account_metas(None)
+}
+
+#[tokio::test]
+async fn pools_are_keyed_by_admin_and_redeem_deposits() {
+    let mut t = setup().await;
+    let owner = t.owner.pubkey();
+    let other_admin = Keypair::new();
+    let owner_keypair = t.owner.insecure_clone();
+    send(
+        &mut t.ctx,
+        &[system_instruction::transfer(&owner, &other_admin.pubkey(), LAMPORTS_PER_SOL)],
+        &owner_keypair,
+        &[&owner_keypair],
+    )
+    .await
+    .unwrap();
+
+    send(&mut t.ctx, &[initialize_pool_ix(&other_admin.pubkey(), &t.mint)], &other_admin, &[&other_admin]).await.unwrap();
+    send(&mut t.ctx, &[initialize_pool_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await.unwrap();
+
+    let (_, pool_vault, share_mint) = pool_pdas(&t.mint, &owner);
+    let deposit = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: pool_payment_accounts(&owner, &owner, &t.mint, &t.owner_ata),
+        data: anchor_vault::instruction::PoolDeposit { amount: 30 * ONE, proof: vec![] }.data(),
+    };
+    send(&mut t.ctx, &[deposit], &t.owner, &[&t.owner]).await.unwrap();
+
+    let user_shares = get_associated_token_address(&owner, &share_mint);
+    let shares = token_balance(&mut t.ctx, &user_shares).await;
+    assert_eq!(token_balance(&mut t.ctx, &pool_vault).await, 30 * ONE);
+    assert_eq!(shares, 30 * ONE);
+
+    let withdraw = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: pool_payment_accounts(&owner, &owner, &t.mint, &t.owner_ata),
+        data: anchor_vault::instruction::PoolWithdraw { shares }.data(),
+    };
+    send(&mut t.ctx, &[withdraw], &t.owner, &[&t.owner]).await.unwrap();
+
+    assert_eq!(token_balance(&mut t.ctx, &user_shares).await, 0);
+    assert_eq!(token_balance(&mut t.ctx, &t.owner_ata).await, 100 * ONE);
+}
+
+#[tokio::test]
+async fn config_caps_track_net_balance() {
+    let mut t = setup().await;
+    let owner = t.owner.pubkey();
+    let (config, bump) = Pubkey::find_program_address(&[b"config", t.mint.as_ref()], &anchor_vault::ID);
+
+    // The config is normally created by the upgrade authority, which a builtin program does not have.
+    let state = anchor_vault::state::VaultConfig {
+        admin: owner,
+        mint: t.mint,
+        per_user_cap: Some(50 * ONE),
+        global_cap: Some(80 * ONE),
+        allowlist_root: None,
+        net_deposited: 0,
+        bump,
+    };
+    let mut data = Vec::new();
+    anchor_lang::AccountSerialize::try_serialize(&state, &mut data).unwrap();
+    let mut account = Account::new(LAMPORTS_PER_SOL, data.len(), &anchor_vault::ID);
+    account.data = data;
+    t.ctx.set_account(&config, &account.into());
+
+    let (mint, owner_ata) = (t.mint, t.owner_ata);
+    let pool_deposit = |amount: u64| Instruction {
+        program_id: anchor_vault::ID,
+        accounts: pool_payment_accounts(&owner, &owner, &mint, &owner_ata),
+        data: anchor_vault::instruction::PoolDeposit { amount, proof: vec![] }.data(),
+    };
+
+    initialize_and_deposit(&mut t, 0, 40 * ONE).await;
+    send(&mut t.ctx, &[initialize_pool_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await.unwrap();
+    assert!(send(&mut t.ctx, &[deposit_ix(&owner, &t.mint, &t.owner_ata, 20 * ONE)], &t.owner, &[&t.owner]).await.is_err());
+    assert!(send(&mut t.ctx, &[pool_deposit(20 * ONE)], &t.owner, &[&t.owner]).await.is_err());
+
+    send(&mut t.ctx, &[withdraw_ix(&owner, &owner, &t.mint, &t.owner_ata, 30 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[deposit_ix(&owner, &t.mint, &t.owner_ata, 20 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    send(&mut t.ctx, &[pool_deposit(20 * ONE)], &t.owner, &[&t.owner]).await.unwrap();
+    assert!(send(&mut t.ctx, &[pool_deposit(ONE)], &t.owner, &[&t.owner]).await.is_err());
+
+    let account = t.ctx.banks_client.get_account(position_pda(&config, &owner)).await.unwrap().unwrap();
+    let position = anchor_vault::state::UserPosition::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(position.net_deposited, 50 * ONE);
+
+    let account = t.ctx.banks_client.get_account(config).await.unwrap().unwrap();
+    let state = anchor_vault::state::VaultConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(state.net_deposited, 50 * ONE);
+}
+
+fn allowance_pda(vault_state: &Pubkey, generation: u64, delegate: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"allowance", vault_state.as_ref(), generation.to_le_bytes().as_ref(), delegate.as_ref()],
+        &anchor_vault::ID,
+    )
+    .0
+}
+
+#[tokio::test]
+async fn reopened_vault_ignores_allowances_from_before_close() {
+    let mut t = setup().await;
+    initialize_and_deposit(&mut t, 0, 0).await;
+    let owner = t.owner.pubkey();
+    let (_, vault_state, vault) = pdas(&owner, &t.mint);
+    let delegate = Keypair::new();
+    let allowance = allowance_pda(&vault_state, 0, &delegate.pubkey());
+
+    let approve = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::ApproveDelegate {
+            signer: owner,
+            delegate: delegate.pubkey(),
+            mint: t.mint,
+            vault_state,
+            allowance,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::ApproveDelegate { amount: 10 * ONE, expires_at: i64::MAX }.data(),
+    };
+    send(&mut t.ctx, &[approve, close_ix(&owner, &t.mint)], &t.owner, &[&t.owner]).await.unwrap();
+    initialize_and_deposit(&mut t, 0, 10 * ONE).await;
+
+    let pull = Instruction {
+        program_id: anchor_vault::ID,
+        accounts: anchor_vault::accounts::DelegatedWithdraw {
+            delegate: delegate.pubkey(),
+            owner,
+            mint: t.mint,
+            destination: t.owner_ata,
+            vault_state,
+            allowance,
+            vault,
+            config: config_pda(&t.mint),
+            user_position: None,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault::instruction::DelegatedWithdraw { amount: 10 * ONE }.data(),
+    };
+    let owner_keypair = t.owner.insecure_clone();
+    assert!(send(&mut t.ctx, &[pull], &owner_keypair, &[&owner_keypair, &delegate]).await.is_err());
+    assert_eq!(token_balance(&mut t.ctx, &vault).await, 10 * ONE);
+}